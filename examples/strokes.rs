@@ -1,4 +1,4 @@
-use {std::io::Result, structopt::StructOpt};
+use {keyboard_layouts::Fallback, std::io::Result, structopt::StructOpt};
 
 #[derive(Debug, StructOpt)]
 #[structopt(
@@ -13,12 +13,23 @@ struct CliOpt {
         default_value = "LAYOUT_US_ENGLISH"
     )]
     layout: String,
+    #[structopt(
+        long = "fallback",
+        short = "u",
+        help = "How to type characters the layout cannot",
+        default_value = "none"
+    )]
+    fallback: Fallback,
     #[structopt(name = "STRING")]
     string: Option<String>,
 }
 
 fn main() -> Result<()> {
-    let CliOpt { layout, string } = CliOpt::from_args();
+    let CliOpt {
+        layout,
+        fallback,
+        string,
+    } = CliOpt::from_args();
 
     if layout.to_lowercase() == "list" {
         for l in keyboard_layouts::available_layouts() {
@@ -30,11 +41,20 @@ fn main() -> Result<()> {
     if let Some(string) = string {
         println!("Layout: {}", layout);
         println!("Keys and Modifiers to type: {}", &string);
-        for key_mod in keyboard_layouts::string_to_keys_and_modifiers(&layout, &string).unwrap() {
-            println!(
-                "Key: {:#02X} Modifier: {:#02X}",
-                key_mod.key, key_mod.modifier
-            );
+        for key_mod in
+            keyboard_layouts::string_to_keys_and_modifiers_with_fallback(&layout, &string, fallback)
+                .unwrap()
+        {
+            match key_mod.fallback {
+                Some(fallback) => println!(
+                    "Key: {:#02X} Modifier: {:#02X} Fallback: {}",
+                    key_mod.key, key_mod.modifier, fallback
+                ),
+                None => println!(
+                    "Key: {:#02X} Modifier: {:#02X}",
+                    key_mod.key, key_mod.modifier
+                ),
+            }
         }
     }
 
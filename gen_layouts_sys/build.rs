@@ -1,6 +1,6 @@
 #![recursion_limit = "128"]
 #[cfg(feature = "generate")]
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, Span, TokenStream};
 #[cfg(feature = "generate")]
 use quote::{quote, ToTokens};
 #[cfg(feature = "generate")]
@@ -16,7 +16,24 @@ const KEY_LAYOUTS_HEADER: &'static str = include_str!("keylayouts.h");
 #[cfg(feature = "generate")]
 const N_ASCII_CHARS_SUPPORTED: usize = 96;
 #[cfg(feature = "generate")]
+const N_LATIN1_CHARS_SUPPORTED: usize = 96;
+#[cfg(feature = "generate")]
 const N_NUMPAD_KEYS: usize = 10;
+#[cfg(feature = "generate")]
+const DEADKEY_PREFIX: &'static str = "DEADKEY_";
+#[cfg(feature = "generate")]
+const KEY_PREFIX: &'static str = "KEY_";
+#[cfg(feature = "generate")]
+const MODIFIER_PREFIX: &'static str = "MODIFIERKEY_";
+#[cfg(feature = "generate")]
+const MEDIA_KEY_PREFIX: &'static str = "KEY_MEDIA_";
+#[cfg(feature = "generate")]
+const SYSTEM_KEY_PREFIX: &'static str = "KEY_SYSTEM_";
+#[cfg(feature = "generate")]
+const KEYPAD_PREFIX: &'static str = "KEYPAD_";
+// Keyboard usage page keys, as opposed to modifiers (0xE000), system (0xE200) and media (0xE400)
+#[cfg(feature = "generate")]
+const KEY_USAGE_PAGE: u16 = 0xF000;
 
 #[cfg(feature = "generate")]
 struct GlobalKeys {
@@ -30,6 +47,12 @@ struct GlobalKeys {
     pub numlock: u16,
 }
 
+#[cfg(feature = "generate")]
+struct NamedKey {
+    pub name: String,
+    pub keycode: u16,
+}
+
 #[cfg(feature = "generate")]
 struct LayoutMasks {
     pub shift_mask: u16,
@@ -41,19 +64,10 @@ struct LayoutMasks {
 }
 
 #[cfg(feature = "generate")]
-struct LayoutDeadKeys {
-    pub acute_accent_bits: Option<u16>,
-    pub deadkey_accute_accent: Option<u16>,
-    pub cedilla_bits: Option<u16>,
-    pub deadkey_cedilla: Option<u16>,
-    pub diaeresis_bits: Option<u16>,
-    pub deadkey_diaeresis: Option<u16>,
-    pub grave_accent_bits: Option<u16>,
-    pub deadkey_grave_accent: Option<u16>,
-    pub circumflex_bits: Option<u16>,
-    pub deadkey_circumflex: Option<u16>,
-    pub tilde_bits: Option<u16>,
-    pub deadkey_tilde: Option<u16>,
+struct LayoutDeadKey {
+    pub accent: String,
+    pub bits: u16,
+    pub keycode: u16,
 }
 
 fn main() {
@@ -63,6 +77,8 @@ fn main() {
 
 #[cfg(feature = "generate")]
 fn generate() {
+    let base_definitions = generate_base_definitions();
+
     let GlobalKeys {
         enter,
         tab,
@@ -72,10 +88,103 @@ fn generate() {
         right_ctrl_modifier,
         numpad_keys,
         numlock,
-    } = get_global_keys();
+    } = get_global_keys(&base_definitions);
+
+    // KEY_PAGE_UP -> PageUp, KEY_1 -> Digit1, KEYPAD_1 -> Keypad1
+    let named_keys = extract_named_keys(&base_definitions);
+    let key_variants = named_keys
+        .iter()
+        .map(|key| {
+            let name = to_camel_case(key.name.trim_start_matches(KEY_PREFIX));
+            let name = if name.starts_with(|c: char| c.is_ascii_digit()) {
+                format!("Digit{}", name)
+            } else {
+                name
+            };
+            Ident::new(&name, Span::call_site())
+        })
+        .collect::<Vec<Ident>>();
+    let key_variants = &key_variants;
+    let key_names = named_keys
+        .iter()
+        .map(|key| &key.name)
+        .collect::<Vec<&String>>();
+    let key_keycodes = named_keys
+        .iter()
+        .map(|key| key.keycode)
+        .collect::<Vec<u16>>();
+    let n_keys = named_keys.len();
 
-    // Layout and DeadKeys come from src/types.rs
-    let layouts = find_layout_definitions()
+    // MODIFIERKEY_LEFT_CTRL -> LeftCtrl. Unlike keys the aliases are kept, e.g. Ctrl and LeftCtrl.
+    let modifiers = extract_constants_with_prefix(&base_definitions, MODIFIER_PREFIX);
+    let modifier_variants = modifiers
+        .iter()
+        .map(|modifier| {
+            Ident::new(
+                &to_camel_case(modifier.name.trim_start_matches(MODIFIER_PREFIX)),
+                Span::call_site(),
+            )
+        })
+        .collect::<Vec<Ident>>();
+    let modifier_variants = &modifier_variants;
+    let modifier_names = modifiers
+        .iter()
+        .map(|modifier| &modifier.name)
+        .collect::<Vec<&String>>();
+    let modifier_keycodes = modifiers
+        .iter()
+        .map(|modifier| modifier.keycode)
+        .collect::<Vec<u16>>();
+    let n_modifiers = modifiers.len();
+
+    // KEY_MEDIA_VOLUME_INC -> VolumeInc
+    let media_keys = extract_constants_with_prefix(&base_definitions, MEDIA_KEY_PREFIX);
+    let media_key_variants = media_keys
+        .iter()
+        .map(|media_key| {
+            Ident::new(
+                &to_camel_case(media_key.name.trim_start_matches(MEDIA_KEY_PREFIX)),
+                Span::call_site(),
+            )
+        })
+        .collect::<Vec<Ident>>();
+    let media_key_variants = &media_key_variants;
+    let media_key_names = media_keys
+        .iter()
+        .map(|media_key| &media_key.name)
+        .collect::<Vec<&String>>();
+    let media_key_keycodes = media_keys
+        .iter()
+        .map(|media_key| media_key.keycode)
+        .collect::<Vec<u16>>();
+    let n_media_keys = media_keys.len();
+
+    // KEY_SYSTEM_POWER_DOWN -> PowerDown
+    let system_keys = extract_constants_with_prefix(&base_definitions, SYSTEM_KEY_PREFIX);
+    let system_key_variants = system_keys
+        .iter()
+        .map(|system_key| {
+            Ident::new(
+                &to_camel_case(system_key.name.trim_start_matches(SYSTEM_KEY_PREFIX)),
+                Span::call_site(),
+            )
+        })
+        .collect::<Vec<Ident>>();
+    let system_key_variants = &system_key_variants;
+    let system_key_names = system_keys
+        .iter()
+        .map(|system_key| &system_key.name)
+        .collect::<Vec<&String>>();
+    let system_key_keycodes = system_keys
+        .iter()
+        .map(|system_key| system_key.keycode)
+        .collect::<Vec<u16>>();
+    let n_system_keys = system_keys.len();
+
+    let layout_defs = find_layout_definitions();
+
+    // Layout, DeadKey and Accent come from src/types.rs
+    let layouts = layout_defs
         .iter()
         .map(|def| {
             let layout = generate_layout(def);
@@ -89,21 +198,6 @@ fn generate() {
                 keycode_mask,
             } = extract_layout_masks(&layout, def);
 
-            let LayoutDeadKeys {
-                acute_accent_bits,
-                deadkey_accute_accent,
-                cedilla_bits,
-                deadkey_cedilla,
-                diaeresis_bits,
-                deadkey_diaeresis,
-                grave_accent_bits,
-                deadkey_grave_accent,
-                circumflex_bits,
-                deadkey_circumflex,
-                tilde_bits,
-                deadkey_tilde,
-            } = extract_layout_deadkeys(&layout);
-
             let keycodes = extract_ascii_keycodes(&layout)
                 .iter()
                 .map(|k| k & keycode_mask)
@@ -118,23 +212,44 @@ fn generate() {
                 N_ASCII_CHARS_SUPPORTED
             );
 
+            // Layouts without an ISO_8859_1 table (e.g. US English) only support ASCII
+            let latin1_keycodes = extract_latin1_keycodes(&layout)
+                .iter()
+                .map(|k| k & keycode_mask)
+                .collect::<Vec<u16>>();
+
+            assert!(
+                latin1_keycodes.is_empty() || latin1_keycodes.len() == N_LATIN1_CHARS_SUPPORTED,
+                "Incomplete ISO-8859-1 keycodes extracted from {}: {}/{}",
+                def,
+                latin1_keycodes.len(),
+                N_LATIN1_CHARS_SUPPORTED
+            );
+
+            let quote_deadkeys = extract_layout_deadkeys(&layout)
+                .iter()
+                .map(|deadkey| {
+                    let accent = Ident::new(&to_camel_case(&deadkey.accent), Span::call_site());
+                    let bits = deadkey.bits;
+                    let keycode = deadkey.keycode & keycode_mask;
+                    quote! { DeadKey::new(Accent::#accent, #bits, #keycode) }
+                })
+                .collect::<Vec<TokenStream>>();
+
+            let quote_extra_keycodes = extract_extra_keycodes(&layout)
+                .iter()
+                .filter(|(_, keycode)| *keycode != 0)
+                .map(|(unicode, keycode)| {
+                    let keycode = keycode & keycode_mask;
+                    quote! { (#unicode, #keycode) }
+                })
+                .collect::<Vec<TokenStream>>();
+
             let layout_key = def.to_string();
             let quote_alt_mask = quote_option(alt_mask);
             let quote_ctrl_mask = quote_option(ctrl_mask);
             let quote_non_us = quote_option(non_us);
             let quote_dead_keys_mask = quote_option(dead_keys_mask);
-            let quote_acute_accent_bits = quote_option(acute_accent_bits);
-            let quote_deadkey_accute_accent = quote_option(deadkey_accute_accent);
-            let quote_cedilla_bits = quote_option(cedilla_bits);
-            let quote_deadkey_cedilla = quote_option(deadkey_cedilla);
-            let quote_diaeresis_bits = quote_option(diaeresis_bits);
-            let quote_deadkey_diaeresis = quote_option(deadkey_diaeresis);
-            let quote_grave_accent_bits = quote_option(grave_accent_bits);
-            let quote_deadkey_grave_accent = quote_option(deadkey_grave_accent);
-            let quote_circumflex_bits = quote_option(circumflex_bits);
-            let quote_deadkey_circumflex = quote_option(deadkey_circumflex);
-            let quote_tilde_bits = quote_option(tilde_bits);
-            let quote_deadkey_tilde = quote_option(deadkey_tilde);
 
             quote! {
                 m.insert(
@@ -147,20 +262,9 @@ fn generate() {
                         #quote_dead_keys_mask,
                         #keycode_mask,
                         vec![#(#keycodes),*],
-                        DeadKeys::new(
-                            #quote_acute_accent_bits,
-                            #quote_deadkey_accute_accent,
-                            #quote_cedilla_bits,
-                            #quote_deadkey_cedilla,
-                            #quote_diaeresis_bits,
-                            #quote_deadkey_diaeresis,
-                            #quote_grave_accent_bits,
-                            #quote_deadkey_grave_accent,
-                            #quote_circumflex_bits,
-                            #quote_deadkey_circumflex,
-                            #quote_tilde_bits,
-                            #quote_deadkey_tilde,
-                        )
+                        vec![#(#latin1_keycodes),*],
+                        vec![#(#quote_extra_keycodes),*],
+                        vec![#(#quote_deadkeys),*],
                     ),
                 );
             }
@@ -174,6 +278,20 @@ fn generate() {
         })
         .collect::<Vec<TokenStream>>();
 
+    // LAYOUT_US_ENGLISH -> UsEnglish
+    let layout_ids = layout_defs
+        .iter()
+        .map(|def| {
+            Ident::new(
+                &to_camel_case(def.trim_start_matches("LAYOUT_")),
+                Span::call_site(),
+            )
+        })
+        .collect::<Vec<Ident>>();
+    let layout_id_variants = &layout_ids;
+    let layout_id_names = &layout_defs;
+    let n_layouts = layout_defs.len();
+
     // Layout comes from src/types.rs
     let full_output = quote! {
         use std::collections::HashMap;
@@ -190,6 +308,115 @@ fn generate() {
             #(#quote_numpad_keys)*
         ];
 
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum LayoutId {
+            #(#layout_id_variants),*
+        }
+
+        impl LayoutId {
+            pub const ALL: [LayoutId; #n_layouts] = [
+                #(LayoutId::#layout_id_variants),*
+            ];
+
+            pub fn name(self) -> &'static str {
+                match self {
+                    #(LayoutId::#layout_id_variants => #layout_id_names),*
+                }
+            }
+        }
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Key {
+            #(#key_variants),*
+        }
+
+        impl Key {
+            pub const ALL: [Key; #n_keys] = [
+                #(Key::#key_variants),*
+            ];
+
+            pub fn name(self) -> &'static str {
+                match self {
+                    #(Key::#key_variants => #key_names),*
+                }
+            }
+
+            pub fn keycode(self) -> u16 {
+                match self {
+                    #(Key::#key_variants => #key_keycodes),*
+                }
+            }
+        }
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Modifier {
+            #(#modifier_variants),*
+        }
+
+        impl Modifier {
+            pub const ALL: [Modifier; #n_modifiers] = [
+                #(Modifier::#modifier_variants),*
+            ];
+
+            pub fn name(self) -> &'static str {
+                match self {
+                    #(Modifier::#modifier_variants => #modifier_names),*
+                }
+            }
+
+            pub fn keycode(self) -> u16 {
+                match self {
+                    #(Modifier::#modifier_variants => #modifier_keycodes),*
+                }
+            }
+        }
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum MediaKey {
+            #(#media_key_variants),*
+        }
+
+        impl MediaKey {
+            pub const ALL: [MediaKey; #n_media_keys] = [
+                #(MediaKey::#media_key_variants),*
+            ];
+
+            pub fn name(self) -> &'static str {
+                match self {
+                    #(MediaKey::#media_key_variants => #media_key_names),*
+                }
+            }
+
+            pub fn keycode(self) -> u16 {
+                match self {
+                    #(MediaKey::#media_key_variants => #media_key_keycodes),*
+                }
+            }
+        }
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum SystemKey {
+            #(#system_key_variants),*
+        }
+
+        impl SystemKey {
+            pub const ALL: [SystemKey; #n_system_keys] = [
+                #(SystemKey::#system_key_variants),*
+            ];
+
+            pub fn name(self) -> &'static str {
+                match self {
+                    #(SystemKey::#system_key_variants => #system_key_names),*
+                }
+            }
+
+            pub fn keycode(self) -> u16 {
+                match self {
+                    #(SystemKey::#system_key_variants => #system_key_keycodes),*
+                }
+            }
+        }
+
         lazy_static! {
             pub static ref LAYOUT_MAP: HashMap<&'static str, Layout> = {
                 let mut m = HashMap::new();
@@ -237,6 +464,34 @@ fn extract_ascii_keycodes(definitions: &syn::File) -> Vec<u16> {
         .collect()
 }
 
+#[cfg(feature = "generate")]
+fn extract_latin1_keycodes(definitions: &syn::File) -> Vec<u16> {
+    definitions
+        .items
+        .iter()
+        .filter_map(|item| find_const_u16_with_name_containing(item, "ISO_8859_1_"))
+        .collect()
+}
+
+// Pairs each UNICODE_EXTRAxx code point with its KEYCODE_EXTRAxx keycode
+#[cfg(feature = "generate")]
+fn extract_extra_keycodes(definitions: &syn::File) -> Vec<(u16, u16)> {
+    definitions
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Const(c) if c.ident.to_string().starts_with("UNICODE_EXTRA") => {
+                let ident = c.ident.to_string();
+                let unicode = find_const_u16_with_name_containing(item, &ident)?;
+                let keycode_label = ident.replace("UNICODE_EXTRA", "KEYCODE_EXTRA");
+                let keycode = find_key_definition(definitions, &keycode_label)?;
+                Some((unicode, keycode))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
 #[cfg(feature = "generate")]
 fn extract_layout_masks(definitions: &syn::File, layout: &str) -> LayoutMasks {
     LayoutMasks {
@@ -251,26 +506,86 @@ fn extract_layout_masks(definitions: &syn::File, layout: &str) -> LayoutMasks {
     }
 }
 
+// Pairs each DEADKEY_<ACCENT> keycode with its <ACCENT>_BITS
+#[cfg(feature = "generate")]
+fn extract_layout_deadkeys(definitions: &syn::File) -> Vec<LayoutDeadKey> {
+    definitions
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Const(c) if c.ident.to_string().starts_with(DEADKEY_PREFIX) => {
+                let ident = c.ident.to_string();
+                let accent = ident.trim_start_matches(DEADKEY_PREFIX).to_string();
+                let keycode = find_const_u16_with_name_containing(item, &ident)?;
+                let bits = find_key_definition(definitions, &format!("{}_BITS", accent))?;
+                Some(LayoutDeadKey {
+                    accent,
+                    bits,
+                    keycode,
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+// ACUTE_ACCENT -> AcuteAccent
+#[cfg(feature = "generate")]
+fn to_camel_case(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_string() + &chars.as_str().to_lowercase(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+// Named keys in header order. Aliases such as KEY_UP_ARROW are skipped in favour of the first
+// name defined for the key.
 #[cfg(feature = "generate")]
-fn extract_layout_deadkeys(definitions: &syn::File) -> LayoutDeadKeys {
-    LayoutDeadKeys {
-        acute_accent_bits: find_key_definition(definitions, "ACUTE_ACCENT_BITS"),
-        deadkey_accute_accent: find_key_definition(definitions, "DEADKEY_ACCUTE_ACCENT"),
-        cedilla_bits: find_key_definition(definitions, "CEDILLA_BITS"),
-        deadkey_cedilla: find_key_definition(definitions, "DEADKEY_CEDILLA"),
-        diaeresis_bits: find_key_definition(definitions, "DIAERESIS_BITS"),
-        deadkey_diaeresis: find_key_definition(definitions, "DEADKEY_DIAERESIS"),
-        grave_accent_bits: find_key_definition(definitions, "GRAVE_ACCENT_BITS"),
-        deadkey_grave_accent: find_key_definition(definitions, "DEADKEY_GRAVE_ACCENT"),
-        circumflex_bits: find_key_definition(definitions, "CIRCUMFLEX_BITS"),
-        deadkey_circumflex: find_key_definition(definitions, "DEADKEY_CIRCUMFLEX"),
-        tilde_bits: find_key_definition(definitions, "TILDE_BITS"),
-        deadkey_tilde: find_key_definition(definitions, "DEADKEY_TILDE"),
+fn extract_named_keys(definitions: &syn::File) -> Vec<NamedKey> {
+    let mut named_keys: Vec<NamedKey> = Vec::new();
+
+    for item in definitions.items.iter() {
+        if let Item::Const(c) = item {
+            let name = c.ident.to_string();
+            if !name.starts_with(KEY_PREFIX) && !name.starts_with(KEYPAD_PREFIX) {
+                continue;
+            }
+            let keycode = match find_const_u16_with_name_containing(item, &name) {
+                Some(keycode) if keycode & KEY_USAGE_PAGE == KEY_USAGE_PAGE => keycode,
+                _ => continue,
+            };
+            if named_keys.iter().all(|key| key.keycode != keycode) {
+                named_keys.push(NamedKey { name, keycode });
+            }
+        }
     }
+
+    named_keys
+}
+
+#[cfg(feature = "generate")]
+fn extract_constants_with_prefix(definitions: &syn::File, prefix: &str) -> Vec<NamedKey> {
+    definitions
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Const(c) if c.ident.to_string().starts_with(prefix) => {
+                let name = c.ident.to_string();
+                let keycode = find_const_u16_with_name_containing(item, &name)?;
+                Some(NamedKey { name, keycode })
+            }
+            _ => None,
+        })
+        .collect()
 }
 
 #[cfg(feature = "generate")]
-fn get_global_keys() -> GlobalKeys {
+fn generate_base_definitions() -> syn::File {
     let bindings = bindgen::Builder::default()
         .generate_comments(false)
         .header_contents("base.h", KEY_LAYOUTS_HEADER)
@@ -278,8 +593,11 @@ fn get_global_keys() -> GlobalKeys {
         .expect("Unable to generate base bindings")
         .to_string();
 
-    let definitions = syn::parse_str::<syn::File>(&bindings).expect("Failed to parse bindings");
+    syn::parse_str::<syn::File>(&bindings).expect("Failed to parse bindings")
+}
 
+#[cfg(feature = "generate")]
+fn get_global_keys(definitions: &syn::File) -> GlobalKeys {
     GlobalKeys {
         enter: find_key_definition(&definitions, "KEY_ENTER")
             .expect("Failed to find global key: KEY_ENTER"),
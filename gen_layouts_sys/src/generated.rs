@@ -1,4 +1,4 @@
-use crate::{DeadKeys, Layout};
+use crate::{Accent, DeadKey, Layout};
 use lazy_static::lazy_static;
 use std::collections::HashMap;
 
@@ -13,6 +13,703 @@ pub const NUMPAD_KEYS: [u16; 10usize] = [
     61538u16, 61529u16, 61530u16, 61531u16, 61532u16, 61533u16, 61534u16, 61535u16, 61536u16,
     61537u16,
 ];
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LayoutId {
+    UsEnglish,
+    CanadianFrench,
+    CanadianMultilingual,
+    Danish,
+    Finnish,
+    French,
+    FrenchBelgian,
+    FrenchSwiss,
+    German,
+    GermanMac,
+    GermanSwiss,
+    Icelandic,
+    Irish,
+    Italian,
+    Norwegian,
+    Portuguese,
+    PortugueseBrazilian,
+    Spanish,
+    SpanishLatinAmerica,
+    Swedish,
+    Turkish,
+    UnitedKingdom,
+    UsInternational,
+}
+impl LayoutId {
+    pub const ALL: [LayoutId; 23usize] = [
+        LayoutId::UsEnglish,
+        LayoutId::CanadianFrench,
+        LayoutId::CanadianMultilingual,
+        LayoutId::Danish,
+        LayoutId::Finnish,
+        LayoutId::French,
+        LayoutId::FrenchBelgian,
+        LayoutId::FrenchSwiss,
+        LayoutId::German,
+        LayoutId::GermanMac,
+        LayoutId::GermanSwiss,
+        LayoutId::Icelandic,
+        LayoutId::Irish,
+        LayoutId::Italian,
+        LayoutId::Norwegian,
+        LayoutId::Portuguese,
+        LayoutId::PortugueseBrazilian,
+        LayoutId::Spanish,
+        LayoutId::SpanishLatinAmerica,
+        LayoutId::Swedish,
+        LayoutId::Turkish,
+        LayoutId::UnitedKingdom,
+        LayoutId::UsInternational,
+    ];
+    pub fn name(self) -> &'static str {
+        match self {
+            LayoutId::UsEnglish => "LAYOUT_US_ENGLISH",
+            LayoutId::CanadianFrench => "LAYOUT_CANADIAN_FRENCH",
+            LayoutId::CanadianMultilingual => "LAYOUT_CANADIAN_MULTILINGUAL",
+            LayoutId::Danish => "LAYOUT_DANISH",
+            LayoutId::Finnish => "LAYOUT_FINNISH",
+            LayoutId::French => "LAYOUT_FRENCH",
+            LayoutId::FrenchBelgian => "LAYOUT_FRENCH_BELGIAN",
+            LayoutId::FrenchSwiss => "LAYOUT_FRENCH_SWISS",
+            LayoutId::German => "LAYOUT_GERMAN",
+            LayoutId::GermanMac => "LAYOUT_GERMAN_MAC",
+            LayoutId::GermanSwiss => "LAYOUT_GERMAN_SWISS",
+            LayoutId::Icelandic => "LAYOUT_ICELANDIC",
+            LayoutId::Irish => "LAYOUT_IRISH",
+            LayoutId::Italian => "LAYOUT_ITALIAN",
+            LayoutId::Norwegian => "LAYOUT_NORWEGIAN",
+            LayoutId::Portuguese => "LAYOUT_PORTUGUESE",
+            LayoutId::PortugueseBrazilian => "LAYOUT_PORTUGUESE_BRAZILIAN",
+            LayoutId::Spanish => "LAYOUT_SPANISH",
+            LayoutId::SpanishLatinAmerica => "LAYOUT_SPANISH_LATIN_AMERICA",
+            LayoutId::Swedish => "LAYOUT_SWEDISH",
+            LayoutId::Turkish => "LAYOUT_TURKISH",
+            LayoutId::UnitedKingdom => "LAYOUT_UNITED_KINGDOM",
+            LayoutId::UsInternational => "LAYOUT_US_INTERNATIONAL",
+        }
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+    Digit1,
+    Digit2,
+    Digit3,
+    Digit4,
+    Digit5,
+    Digit6,
+    Digit7,
+    Digit8,
+    Digit9,
+    Digit0,
+    Enter,
+    Esc,
+    Backspace,
+    Tab,
+    Space,
+    Minus,
+    Equal,
+    LeftBrace,
+    RightBrace,
+    Backslash,
+    NonUsNum,
+    Semicolon,
+    Quote,
+    Tilde,
+    Comma,
+    Period,
+    Slash,
+    CapsLock,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    Printscreen,
+    ScrollLock,
+    Pause,
+    Insert,
+    Home,
+    PageUp,
+    Delete,
+    End,
+    PageDown,
+    Right,
+    Left,
+    Down,
+    Up,
+    NumLock,
+    KeypadSlash,
+    KeypadAsterix,
+    KeypadMinus,
+    KeypadPlus,
+    KeypadEnter,
+    Keypad1,
+    Keypad2,
+    Keypad3,
+    Keypad4,
+    Keypad5,
+    Keypad6,
+    Keypad7,
+    Keypad8,
+    Keypad9,
+    Keypad0,
+    KeypadPeriod,
+    NonUsBs,
+    Menu,
+    F13,
+    F14,
+    F15,
+    F16,
+    F17,
+    F18,
+    F19,
+    F20,
+    F21,
+    F22,
+    F23,
+    F24,
+}
+impl Key {
+    pub const ALL: [Key; 110usize] = [
+        Key::A,
+        Key::B,
+        Key::C,
+        Key::D,
+        Key::E,
+        Key::F,
+        Key::G,
+        Key::H,
+        Key::I,
+        Key::J,
+        Key::K,
+        Key::L,
+        Key::M,
+        Key::N,
+        Key::O,
+        Key::P,
+        Key::Q,
+        Key::R,
+        Key::S,
+        Key::T,
+        Key::U,
+        Key::V,
+        Key::W,
+        Key::X,
+        Key::Y,
+        Key::Z,
+        Key::Digit1,
+        Key::Digit2,
+        Key::Digit3,
+        Key::Digit4,
+        Key::Digit5,
+        Key::Digit6,
+        Key::Digit7,
+        Key::Digit8,
+        Key::Digit9,
+        Key::Digit0,
+        Key::Enter,
+        Key::Esc,
+        Key::Backspace,
+        Key::Tab,
+        Key::Space,
+        Key::Minus,
+        Key::Equal,
+        Key::LeftBrace,
+        Key::RightBrace,
+        Key::Backslash,
+        Key::NonUsNum,
+        Key::Semicolon,
+        Key::Quote,
+        Key::Tilde,
+        Key::Comma,
+        Key::Period,
+        Key::Slash,
+        Key::CapsLock,
+        Key::F1,
+        Key::F2,
+        Key::F3,
+        Key::F4,
+        Key::F5,
+        Key::F6,
+        Key::F7,
+        Key::F8,
+        Key::F9,
+        Key::F10,
+        Key::F11,
+        Key::F12,
+        Key::Printscreen,
+        Key::ScrollLock,
+        Key::Pause,
+        Key::Insert,
+        Key::Home,
+        Key::PageUp,
+        Key::Delete,
+        Key::End,
+        Key::PageDown,
+        Key::Right,
+        Key::Left,
+        Key::Down,
+        Key::Up,
+        Key::NumLock,
+        Key::KeypadSlash,
+        Key::KeypadAsterix,
+        Key::KeypadMinus,
+        Key::KeypadPlus,
+        Key::KeypadEnter,
+        Key::Keypad1,
+        Key::Keypad2,
+        Key::Keypad3,
+        Key::Keypad4,
+        Key::Keypad5,
+        Key::Keypad6,
+        Key::Keypad7,
+        Key::Keypad8,
+        Key::Keypad9,
+        Key::Keypad0,
+        Key::KeypadPeriod,
+        Key::NonUsBs,
+        Key::Menu,
+        Key::F13,
+        Key::F14,
+        Key::F15,
+        Key::F16,
+        Key::F17,
+        Key::F18,
+        Key::F19,
+        Key::F20,
+        Key::F21,
+        Key::F22,
+        Key::F23,
+        Key::F24,
+    ];
+    pub fn name(self) -> &'static str {
+        match self {
+            Key::A => "KEY_A",
+            Key::B => "KEY_B",
+            Key::C => "KEY_C",
+            Key::D => "KEY_D",
+            Key::E => "KEY_E",
+            Key::F => "KEY_F",
+            Key::G => "KEY_G",
+            Key::H => "KEY_H",
+            Key::I => "KEY_I",
+            Key::J => "KEY_J",
+            Key::K => "KEY_K",
+            Key::L => "KEY_L",
+            Key::M => "KEY_M",
+            Key::N => "KEY_N",
+            Key::O => "KEY_O",
+            Key::P => "KEY_P",
+            Key::Q => "KEY_Q",
+            Key::R => "KEY_R",
+            Key::S => "KEY_S",
+            Key::T => "KEY_T",
+            Key::U => "KEY_U",
+            Key::V => "KEY_V",
+            Key::W => "KEY_W",
+            Key::X => "KEY_X",
+            Key::Y => "KEY_Y",
+            Key::Z => "KEY_Z",
+            Key::Digit1 => "KEY_1",
+            Key::Digit2 => "KEY_2",
+            Key::Digit3 => "KEY_3",
+            Key::Digit4 => "KEY_4",
+            Key::Digit5 => "KEY_5",
+            Key::Digit6 => "KEY_6",
+            Key::Digit7 => "KEY_7",
+            Key::Digit8 => "KEY_8",
+            Key::Digit9 => "KEY_9",
+            Key::Digit0 => "KEY_0",
+            Key::Enter => "KEY_ENTER",
+            Key::Esc => "KEY_ESC",
+            Key::Backspace => "KEY_BACKSPACE",
+            Key::Tab => "KEY_TAB",
+            Key::Space => "KEY_SPACE",
+            Key::Minus => "KEY_MINUS",
+            Key::Equal => "KEY_EQUAL",
+            Key::LeftBrace => "KEY_LEFT_BRACE",
+            Key::RightBrace => "KEY_RIGHT_BRACE",
+            Key::Backslash => "KEY_BACKSLASH",
+            Key::NonUsNum => "KEY_NON_US_NUM",
+            Key::Semicolon => "KEY_SEMICOLON",
+            Key::Quote => "KEY_QUOTE",
+            Key::Tilde => "KEY_TILDE",
+            Key::Comma => "KEY_COMMA",
+            Key::Period => "KEY_PERIOD",
+            Key::Slash => "KEY_SLASH",
+            Key::CapsLock => "KEY_CAPS_LOCK",
+            Key::F1 => "KEY_F1",
+            Key::F2 => "KEY_F2",
+            Key::F3 => "KEY_F3",
+            Key::F4 => "KEY_F4",
+            Key::F5 => "KEY_F5",
+            Key::F6 => "KEY_F6",
+            Key::F7 => "KEY_F7",
+            Key::F8 => "KEY_F8",
+            Key::F9 => "KEY_F9",
+            Key::F10 => "KEY_F10",
+            Key::F11 => "KEY_F11",
+            Key::F12 => "KEY_F12",
+            Key::Printscreen => "KEY_PRINTSCREEN",
+            Key::ScrollLock => "KEY_SCROLL_LOCK",
+            Key::Pause => "KEY_PAUSE",
+            Key::Insert => "KEY_INSERT",
+            Key::Home => "KEY_HOME",
+            Key::PageUp => "KEY_PAGE_UP",
+            Key::Delete => "KEY_DELETE",
+            Key::End => "KEY_END",
+            Key::PageDown => "KEY_PAGE_DOWN",
+            Key::Right => "KEY_RIGHT",
+            Key::Left => "KEY_LEFT",
+            Key::Down => "KEY_DOWN",
+            Key::Up => "KEY_UP",
+            Key::NumLock => "KEY_NUM_LOCK",
+            Key::KeypadSlash => "KEYPAD_SLASH",
+            Key::KeypadAsterix => "KEYPAD_ASTERIX",
+            Key::KeypadMinus => "KEYPAD_MINUS",
+            Key::KeypadPlus => "KEYPAD_PLUS",
+            Key::KeypadEnter => "KEYPAD_ENTER",
+            Key::Keypad1 => "KEYPAD_1",
+            Key::Keypad2 => "KEYPAD_2",
+            Key::Keypad3 => "KEYPAD_3",
+            Key::Keypad4 => "KEYPAD_4",
+            Key::Keypad5 => "KEYPAD_5",
+            Key::Keypad6 => "KEYPAD_6",
+            Key::Keypad7 => "KEYPAD_7",
+            Key::Keypad8 => "KEYPAD_8",
+            Key::Keypad9 => "KEYPAD_9",
+            Key::Keypad0 => "KEYPAD_0",
+            Key::KeypadPeriod => "KEYPAD_PERIOD",
+            Key::NonUsBs => "KEY_NON_US_BS",
+            Key::Menu => "KEY_MENU",
+            Key::F13 => "KEY_F13",
+            Key::F14 => "KEY_F14",
+            Key::F15 => "KEY_F15",
+            Key::F16 => "KEY_F16",
+            Key::F17 => "KEY_F17",
+            Key::F18 => "KEY_F18",
+            Key::F19 => "KEY_F19",
+            Key::F20 => "KEY_F20",
+            Key::F21 => "KEY_F21",
+            Key::F22 => "KEY_F22",
+            Key::F23 => "KEY_F23",
+            Key::F24 => "KEY_F24",
+        }
+    }
+    pub fn keycode(self) -> u16 {
+        match self {
+            Key::A => 61444u16,
+            Key::B => 61445u16,
+            Key::C => 61446u16,
+            Key::D => 61447u16,
+            Key::E => 61448u16,
+            Key::F => 61449u16,
+            Key::G => 61450u16,
+            Key::H => 61451u16,
+            Key::I => 61452u16,
+            Key::J => 61453u16,
+            Key::K => 61454u16,
+            Key::L => 61455u16,
+            Key::M => 61456u16,
+            Key::N => 61457u16,
+            Key::O => 61458u16,
+            Key::P => 61459u16,
+            Key::Q => 61460u16,
+            Key::R => 61461u16,
+            Key::S => 61462u16,
+            Key::T => 61463u16,
+            Key::U => 61464u16,
+            Key::V => 61465u16,
+            Key::W => 61466u16,
+            Key::X => 61467u16,
+            Key::Y => 61468u16,
+            Key::Z => 61469u16,
+            Key::Digit1 => 61470u16,
+            Key::Digit2 => 61471u16,
+            Key::Digit3 => 61472u16,
+            Key::Digit4 => 61473u16,
+            Key::Digit5 => 61474u16,
+            Key::Digit6 => 61475u16,
+            Key::Digit7 => 61476u16,
+            Key::Digit8 => 61477u16,
+            Key::Digit9 => 61478u16,
+            Key::Digit0 => 61479u16,
+            Key::Enter => 61480u16,
+            Key::Esc => 61481u16,
+            Key::Backspace => 61482u16,
+            Key::Tab => 61483u16,
+            Key::Space => 61484u16,
+            Key::Minus => 61485u16,
+            Key::Equal => 61486u16,
+            Key::LeftBrace => 61487u16,
+            Key::RightBrace => 61488u16,
+            Key::Backslash => 61489u16,
+            Key::NonUsNum => 61490u16,
+            Key::Semicolon => 61491u16,
+            Key::Quote => 61492u16,
+            Key::Tilde => 61493u16,
+            Key::Comma => 61494u16,
+            Key::Period => 61495u16,
+            Key::Slash => 61496u16,
+            Key::CapsLock => 61497u16,
+            Key::F1 => 61498u16,
+            Key::F2 => 61499u16,
+            Key::F3 => 61500u16,
+            Key::F4 => 61501u16,
+            Key::F5 => 61502u16,
+            Key::F6 => 61503u16,
+            Key::F7 => 61504u16,
+            Key::F8 => 61505u16,
+            Key::F9 => 61506u16,
+            Key::F10 => 61507u16,
+            Key::F11 => 61508u16,
+            Key::F12 => 61509u16,
+            Key::Printscreen => 61510u16,
+            Key::ScrollLock => 61511u16,
+            Key::Pause => 61512u16,
+            Key::Insert => 61513u16,
+            Key::Home => 61514u16,
+            Key::PageUp => 61515u16,
+            Key::Delete => 61516u16,
+            Key::End => 61517u16,
+            Key::PageDown => 61518u16,
+            Key::Right => 61519u16,
+            Key::Left => 61520u16,
+            Key::Down => 61521u16,
+            Key::Up => 61522u16,
+            Key::NumLock => 61523u16,
+            Key::KeypadSlash => 61524u16,
+            Key::KeypadAsterix => 61525u16,
+            Key::KeypadMinus => 61526u16,
+            Key::KeypadPlus => 61527u16,
+            Key::KeypadEnter => 61528u16,
+            Key::Keypad1 => 61529u16,
+            Key::Keypad2 => 61530u16,
+            Key::Keypad3 => 61531u16,
+            Key::Keypad4 => 61532u16,
+            Key::Keypad5 => 61533u16,
+            Key::Keypad6 => 61534u16,
+            Key::Keypad7 => 61535u16,
+            Key::Keypad8 => 61536u16,
+            Key::Keypad9 => 61537u16,
+            Key::Keypad0 => 61538u16,
+            Key::KeypadPeriod => 61539u16,
+            Key::NonUsBs => 61540u16,
+            Key::Menu => 61541u16,
+            Key::F13 => 61544u16,
+            Key::F14 => 61545u16,
+            Key::F15 => 61546u16,
+            Key::F16 => 61547u16,
+            Key::F17 => 61548u16,
+            Key::F18 => 61549u16,
+            Key::F19 => 61550u16,
+            Key::F20 => 61551u16,
+            Key::F21 => 61552u16,
+            Key::F22 => 61553u16,
+            Key::F23 => 61554u16,
+            Key::F24 => 61555u16,
+        }
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Modifier {
+    Ctrl,
+    Shift,
+    Alt,
+    Gui,
+    LeftCtrl,
+    LeftShift,
+    LeftAlt,
+    LeftGui,
+    RightCtrl,
+    RightShift,
+    RightAlt,
+    RightGui,
+}
+impl Modifier {
+    pub const ALL: [Modifier; 12usize] = [
+        Modifier::Ctrl,
+        Modifier::Shift,
+        Modifier::Alt,
+        Modifier::Gui,
+        Modifier::LeftCtrl,
+        Modifier::LeftShift,
+        Modifier::LeftAlt,
+        Modifier::LeftGui,
+        Modifier::RightCtrl,
+        Modifier::RightShift,
+        Modifier::RightAlt,
+        Modifier::RightGui,
+    ];
+    pub fn name(self) -> &'static str {
+        match self {
+            Modifier::Ctrl => "MODIFIERKEY_CTRL",
+            Modifier::Shift => "MODIFIERKEY_SHIFT",
+            Modifier::Alt => "MODIFIERKEY_ALT",
+            Modifier::Gui => "MODIFIERKEY_GUI",
+            Modifier::LeftCtrl => "MODIFIERKEY_LEFT_CTRL",
+            Modifier::LeftShift => "MODIFIERKEY_LEFT_SHIFT",
+            Modifier::LeftAlt => "MODIFIERKEY_LEFT_ALT",
+            Modifier::LeftGui => "MODIFIERKEY_LEFT_GUI",
+            Modifier::RightCtrl => "MODIFIERKEY_RIGHT_CTRL",
+            Modifier::RightShift => "MODIFIERKEY_RIGHT_SHIFT",
+            Modifier::RightAlt => "MODIFIERKEY_RIGHT_ALT",
+            Modifier::RightGui => "MODIFIERKEY_RIGHT_GUI",
+        }
+    }
+    pub fn keycode(self) -> u16 {
+        match self {
+            Modifier::Ctrl => 57345u16,
+            Modifier::Shift => 57346u16,
+            Modifier::Alt => 57348u16,
+            Modifier::Gui => 57352u16,
+            Modifier::LeftCtrl => 57345u16,
+            Modifier::LeftShift => 57346u16,
+            Modifier::LeftAlt => 57348u16,
+            Modifier::LeftGui => 57352u16,
+            Modifier::RightCtrl => 57360u16,
+            Modifier::RightShift => 57376u16,
+            Modifier::RightAlt => 57408u16,
+            Modifier::RightGui => 57472u16,
+        }
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MediaKey {
+    Play,
+    Pause,
+    Record,
+    FastForward,
+    Rewind,
+    NextTrack,
+    PrevTrack,
+    Stop,
+    Eject,
+    RandomPlay,
+    PlayPause,
+    PlaySkip,
+    Mute,
+    VolumeInc,
+    VolumeDec,
+}
+impl MediaKey {
+    pub const ALL: [MediaKey; 15usize] = [
+        MediaKey::Play,
+        MediaKey::Pause,
+        MediaKey::Record,
+        MediaKey::FastForward,
+        MediaKey::Rewind,
+        MediaKey::NextTrack,
+        MediaKey::PrevTrack,
+        MediaKey::Stop,
+        MediaKey::Eject,
+        MediaKey::RandomPlay,
+        MediaKey::PlayPause,
+        MediaKey::PlaySkip,
+        MediaKey::Mute,
+        MediaKey::VolumeInc,
+        MediaKey::VolumeDec,
+    ];
+    pub fn name(self) -> &'static str {
+        match self {
+            MediaKey::Play => "KEY_MEDIA_PLAY",
+            MediaKey::Pause => "KEY_MEDIA_PAUSE",
+            MediaKey::Record => "KEY_MEDIA_RECORD",
+            MediaKey::FastForward => "KEY_MEDIA_FAST_FORWARD",
+            MediaKey::Rewind => "KEY_MEDIA_REWIND",
+            MediaKey::NextTrack => "KEY_MEDIA_NEXT_TRACK",
+            MediaKey::PrevTrack => "KEY_MEDIA_PREV_TRACK",
+            MediaKey::Stop => "KEY_MEDIA_STOP",
+            MediaKey::Eject => "KEY_MEDIA_EJECT",
+            MediaKey::RandomPlay => "KEY_MEDIA_RANDOM_PLAY",
+            MediaKey::PlayPause => "KEY_MEDIA_PLAY_PAUSE",
+            MediaKey::PlaySkip => "KEY_MEDIA_PLAY_SKIP",
+            MediaKey::Mute => "KEY_MEDIA_MUTE",
+            MediaKey::VolumeInc => "KEY_MEDIA_VOLUME_INC",
+            MediaKey::VolumeDec => "KEY_MEDIA_VOLUME_DEC",
+        }
+    }
+    pub fn keycode(self) -> u16 {
+        match self {
+            MediaKey::Play => 58544u16,
+            MediaKey::Pause => 58545u16,
+            MediaKey::Record => 58546u16,
+            MediaKey::FastForward => 58547u16,
+            MediaKey::Rewind => 58548u16,
+            MediaKey::NextTrack => 58549u16,
+            MediaKey::PrevTrack => 58550u16,
+            MediaKey::Stop => 58551u16,
+            MediaKey::Eject => 58552u16,
+            MediaKey::RandomPlay => 58553u16,
+            MediaKey::PlayPause => 58573u16,
+            MediaKey::PlaySkip => 58574u16,
+            MediaKey::Mute => 58594u16,
+            MediaKey::VolumeInc => 58601u16,
+            MediaKey::VolumeDec => 58602u16,
+        }
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SystemKey {
+    PowerDown,
+    Sleep,
+    WakeUp,
+}
+impl SystemKey {
+    pub const ALL: [SystemKey; 3usize] =
+        [SystemKey::PowerDown, SystemKey::Sleep, SystemKey::WakeUp];
+    pub fn name(self) -> &'static str {
+        match self {
+            SystemKey::PowerDown => "KEY_SYSTEM_POWER_DOWN",
+            SystemKey::Sleep => "KEY_SYSTEM_SLEEP",
+            SystemKey::WakeUp => "KEY_SYSTEM_WAKE_UP",
+        }
+    }
+    pub fn keycode(self) -> u16 {
+        match self {
+            SystemKey::PowerDown => 57985u16,
+            SystemKey::Sleep => 57986u16,
+            SystemKey::WakeUp => 57987u16,
+        }
+    }
+}
 lazy_static! {
     pub static ref LAYOUT_MAP: HashMap<&'static str, Layout> = {
         let mut m = HashMap::new();
@@ -36,9 +733,9 @@ lazy_static! {
                     15u16, 16u16, 17u16, 18u16, 19u16, 20u16, 21u16, 22u16, 23u16, 24u16, 25u16,
                     26u16, 27u16, 28u16, 29u16, 111u16, 113u16, 112u16, 117u16, 42u16,
                 ],
-                DeadKeys::new(
-                    None, None, None, None, None, None, None, None, None, None, None, None,
-                ),
+                vec![],
+                vec![],
+                vec![],
             ),
         );
         m.insert(
@@ -61,20 +758,26 @@ lazy_static! {
                     15u16, 16u16, 17u16, 18u16, 19u16, 20u16, 21u16, 22u16, 23u16, 24u16, 25u16,
                     26u16, 27u16, 28u16, 29u16, 180u16, 117u16, 177u16, 179u16, 42u16,
                 ],
-                DeadKeys::new(
-                    Some(512u16),
-                    None,
-                    Some(1280u16),
-                    Some(61488u16),
-                    Some(1024u16),
-                    Some(61552u16),
-                    Some(768u16),
-                    Some(61492u16),
-                    Some(256u16),
-                    Some(61487u16),
-                    None,
-                    None,
-                ),
+                vec![
+                    44u16, 0u16, 161u16, 160u16, 162u16, 0u16, 164u16, 146u16, 0u16, 0u16, 0u16,
+                    63u16, 163u16, 183u16, 0u16, 182u16, 191u16, 158u16, 165u16, 166u16, 0u16,
+                    144u16, 147u16, 0u16, 0u16, 0u16, 0u16, 127u16, 167u16, 173u16, 174u16, 0u16,
+                    836u16, 580u16, 324u16, 0u16, 1092u16, 0u16, 0u16, 1350u16, 840u16, 120u16,
+                    328u16, 1096u16, 844u16, 588u16, 332u16, 1100u16, 0u16, 0u16, 850u16, 594u16,
+                    338u16, 0u16, 1106u16, 0u16, 0u16, 856u16, 600u16, 344u16, 1048u16, 604u16,
+                    0u16, 0u16, 772u16, 516u16, 260u16, 0u16, 1028u16, 0u16, 0u16, 1286u16, 776u16,
+                    56u16, 264u16, 1032u16, 780u16, 524u16, 268u16, 1036u16, 0u16, 0u16, 786u16,
+                    530u16, 274u16, 0u16, 1042u16, 0u16, 0u16, 792u16, 536u16, 280u16, 1048u16,
+                    540u16, 0u16, 1052u16,
+                ],
+                vec![],
+                vec![
+                    DeadKey::new(Accent::Circumflex, 256u16, 47u16),
+                    DeadKey::new(Accent::AcuteAccent, 512u16, 184u16),
+                    DeadKey::new(Accent::GraveAccent, 768u16, 52u16),
+                    DeadKey::new(Accent::Diaeresis, 1024u16, 112u16),
+                    DeadKey::new(Accent::Cedilla, 1280u16, 48u16),
+                ],
             ),
         );
         m.insert(
@@ -97,20 +800,29 @@ lazy_static! {
                     15u16, 16u16, 17u16, 18u16, 19u16, 20u16, 21u16, 22u16, 23u16, 24u16, 25u16,
                     26u16, 27u16, 28u16, 29u16, 164u16, 181u16, 165u16, 2096u16, 42u16,
                 ],
-                DeadKeys::new(
-                    Some(768u16),
-                    None,
-                    Some(1024u16),
-                    Some(63534u16),
-                    Some(512u16),
-                    Some(61551u16),
-                    Some(1280u16),
-                    Some(61615u16),
-                    Some(256u16),
-                    Some(61487u16),
-                    Some(1536u16),
-                    Some(61616u16),
-                ),
+                vec![
+                    172u16, 2142u16, 2054u16, 2144u16, 2145u16, 2140u16, 2175u16, 2134u16, 556u16,
+                    2118u16, 2121u16, 157u16, 174u16, 2165u16, 2133u16, 0u16, 179u16, 2150u16,
+                    2079u16, 2080u16, 812u16, 2064u16, 2069u16, 0u16, 1068u16, 2078u16, 2128u16,
+                    155u16, 2081u16, 2082u16, 2083u16, 2157u16, 113u16, 836u16, 324u16, 1604u16,
+                    580u16, 1860u16, 2116u16, 112u16, 116u16, 120u16, 328u16, 584u16, 1356u16,
+                    844u16, 332u16, 588u16, 2119u16, 1617u16, 1362u16, 850u16, 338u16, 1618u16,
+                    594u16, 2166u16, 2130u16, 127u16, 856u16, 344u16, 536u16, 860u16, 2131u16,
+                    2070u16, 49u16, 772u16, 260u16, 1540u16, 516u16, 1796u16, 2052u16, 48u16,
+                    52u16, 56u16, 264u16, 520u16, 1292u16, 780u16, 268u16, 524u16, 2055u16,
+                    1553u16, 1298u16, 786u16, 274u16, 1554u16, 530u16, 2167u16, 2066u16, 63u16,
+                    792u16, 280u16, 536u16, 796u16, 2067u16, 540u16,
+                ],
+                vec![],
+                vec![
+                    DeadKey::new(Accent::Circumflex, 256u16, 47u16),
+                    DeadKey::new(Accent::Diaeresis, 512u16, 111u16),
+                    DeadKey::new(Accent::AcuteAccent, 768u16, 2099u16),
+                    DeadKey::new(Accent::Cedilla, 1024u16, 2094u16),
+                    DeadKey::new(Accent::GraveAccent, 1280u16, 175u16),
+                    DeadKey::new(Accent::Tilde, 1536u16, 176u16),
+                    DeadKey::new(Accent::RingAbove, 1792u16, 2159u16),
+                ],
             ),
         );
         m.insert(
@@ -133,20 +845,26 @@ lazy_static! {
                     14u16, 15u16, 16u16, 17u16, 18u16, 19u16, 20u16, 21u16, 22u16, 23u16, 24u16,
                     25u16, 26u16, 27u16, 28u16, 29u16, 164u16, 174u16, 167u16, 1068u16, 42u16,
                 ],
-                DeadKeys::new(
-                    Some(512u16),
-                    None,
-                    None,
-                    None,
-                    Some(1280u16),
-                    Some(61488u16),
-                    Some(768u16),
-                    Some(61550u16),
-                    Some(256u16),
-                    Some(61552u16),
-                    Some(1024u16),
-                    Some(61616u16),
-                ),
+                vec![
+                    44u16, 0u16, 0u16, 160u16, 97u16, 0u16, 0u16, 117u16, 1324u16, 0u16, 0u16,
+                    33u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 556u16, 144u16, 0u16,
+                    0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 53u16, 0u16, 0u16, 836u16, 580u16, 324u16,
+                    1092u16, 1348u16, 111u16, 115u16, 0u16, 840u16, 584u16, 328u16, 1352u16,
+                    844u16, 588u16, 332u16, 1356u16, 199u16, 1105u16, 850u16, 594u16, 338u16,
+                    1106u16, 1362u16, 0u16, 116u16, 856u16, 600u16, 344u16, 1368u16, 604u16,
+                    215u16, 150u16, 772u16, 516u16, 260u16, 1028u16, 1284u16, 47u16, 51u16, 0u16,
+                    776u16, 520u16, 264u16, 1288u16, 780u16, 524u16, 268u16, 1292u16, 135u16,
+                    1041u16, 786u16, 530u16, 274u16, 1042u16, 1298u16, 0u16, 52u16, 792u16, 536u16,
+                    280u16, 1304u16, 540u16, 151u16, 1308u16,
+                ],
+                vec![(8364u16, 136u16)],
+                vec![
+                    DeadKey::new(Accent::Circumflex, 256u16, 112u16),
+                    DeadKey::new(Accent::AcuteAccent, 512u16, 46u16),
+                    DeadKey::new(Accent::GraveAccent, 768u16, 110u16),
+                    DeadKey::new(Accent::Tilde, 1024u16, 176u16),
+                    DeadKey::new(Accent::Diaeresis, 1280u16, 48u16),
+                ],
             ),
         );
         m.insert(
@@ -169,20 +887,26 @@ lazy_static! {
                     14u16, 15u16, 16u16, 17u16, 18u16, 19u16, 20u16, 21u16, 22u16, 23u16, 24u16,
                     25u16, 26u16, 27u16, 28u16, 29u16, 164u16, 191u16, 167u16, 1068u16, 42u16,
                 ],
-                DeadKeys::new(
-                    Some(512u16),
-                    None,
-                    None,
-                    None,
-                    Some(1280u16),
-                    Some(61488u16),
-                    Some(768u16),
-                    Some(61550u16),
-                    Some(256u16),
-                    Some(61552u16),
-                    Some(1024u16),
-                    Some(61616u16),
-                ),
+                vec![
+                    172u16, 222u16, 0u16, 160u16, 97u16, 0u16, 0u16, 53u16, 1324u16, 0u16, 0u16,
+                    225u16, 0u16, 184u16, 0u16, 0u16, 231u16, 0u16, 0u16, 0u16, 556u16, 144u16,
+                    0u16, 219u16, 0u16, 0u16, 0u16, 224u16, 0u16, 117u16, 0u16, 237u16, 836u16,
+                    580u16, 324u16, 1092u16, 116u16, 111u16, 244u16, 0u16, 840u16, 584u16, 328u16,
+                    1352u16, 844u16, 588u16, 332u16, 1356u16, 199u16, 1105u16, 850u16, 594u16,
+                    338u16, 1106u16, 115u16, 155u16, 243u16, 856u16, 600u16, 344u16, 1368u16,
+                    604u16, 215u16, 150u16, 772u16, 516u16, 260u16, 1028u16, 52u16, 47u16, 180u16,
+                    0u16, 776u16, 520u16, 264u16, 1288u16, 780u16, 524u16, 268u16, 1292u16, 135u16,
+                    1041u16, 786u16, 530u16, 274u16, 1042u16, 51u16, 0u16, 179u16, 792u16, 536u16,
+                    280u16, 1304u16, 540u16, 151u16, 1308u16,
+                ],
+                vec![(8364u16, 136u16)],
+                vec![
+                    DeadKey::new(Accent::Circumflex, 256u16, 112u16),
+                    DeadKey::new(Accent::AcuteAccent, 512u16, 46u16),
+                    DeadKey::new(Accent::GraveAccent, 768u16, 110u16),
+                    DeadKey::new(Accent::Tilde, 1024u16, 176u16),
+                    DeadKey::new(Accent::Diaeresis, 1280u16, 48u16),
+                ],
             ),
         );
         m.insert(
@@ -205,20 +929,24 @@ lazy_static! {
                     14u16, 15u16, 51u16, 17u16, 18u16, 19u16, 4u16, 21u16, 22u16, 23u16, 24u16,
                     25u16, 29u16, 27u16, 28u16, 26u16, 161u16, 163u16, 174u16, 1068u16, 42u16,
                 ],
-                DeadKeys::new(
-                    None,
-                    None,
-                    None,
-                    None,
-                    Some(768u16),
-                    Some(61551u16),
-                    Some(512u16),
-                    Some(61604u16),
-                    Some(256u16),
-                    Some(61487u16),
-                    Some(1024u16),
-                    Some(61599u16),
-                ),
+                vec![
+                    44u16, 0u16, 0u16, 112u16, 176u16, 0u16, 0u16, 120u16, 812u16, 0u16, 0u16,
+                    0u16, 0u16, 0u16, 0u16, 0u16, 109u16, 0u16, 53u16, 0u16, 0u16, 113u16, 0u16,
+                    0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 596u16, 0u16, 340u16,
+                    1108u16, 852u16, 0u16, 0u16, 0u16, 584u16, 0u16, 328u16, 840u16, 588u16, 0u16,
+                    332u16, 844u16, 0u16, 1105u16, 594u16, 0u16, 338u16, 1106u16, 850u16, 0u16,
+                    0u16, 600u16, 0u16, 344u16, 792u16, 0u16, 0u16, 0u16, 39u16, 0u16, 276u16,
+                    1044u16, 788u16, 0u16, 0u16, 38u16, 36u16, 31u16, 264u16, 776u16, 524u16, 0u16,
+                    268u16, 780u16, 0u16, 1041u16, 530u16, 0u16, 274u16, 1042u16, 786u16, 0u16,
+                    0u16, 51u16, 0u16, 280u16, 792u16, 0u16, 0u16, 796u16,
+                ],
+                vec![(8364u16, 136u16)],
+                vec![
+                    DeadKey::new(Accent::Circumflex, 256u16, 47u16),
+                    DeadKey::new(Accent::GraveAccent, 512u16, 164u16),
+                    DeadKey::new(Accent::Diaeresis, 768u16, 111u16),
+                    DeadKey::new(Accent::Tilde, 1024u16, 159u16),
+                ],
             ),
         );
         m.insert(
@@ -242,20 +970,26 @@ lazy_static! {
                     24u16, 25u16, 29u16, 27u16, 28u16, 26u16, 166u16, 158u16, 167u16, 1068u16,
                     42u16,
                 ],
-                DeadKeys::new(
-                    Some(1280u16),
-                    None,
-                    None,
-                    None,
-                    Some(768u16),
-                    Some(61551u16),
-                    Some(512u16),
-                    Some(61617u16),
-                    Some(256u16),
-                    Some(61487u16),
-                    Some(1024u16),
-                    Some(61624u16),
-                ),
+                vec![
+                    44u16, 0u16, 0u16, 113u16, 0u16, 0u16, 0u16, 35u16, 812u16, 0u16, 0u16, 0u16,
+                    0u16, 0u16, 0u16, 0u16, 109u16, 0u16, 53u16, 117u16, 1324u16, 49u16, 0u16,
+                    0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 596u16, 1364u16, 340u16,
+                    1108u16, 852u16, 0u16, 0u16, 0u16, 584u16, 1352u16, 328u16, 840u16, 588u16,
+                    1356u16, 332u16, 844u16, 0u16, 1105u16, 594u16, 1362u16, 338u16, 1106u16,
+                    850u16, 0u16, 0u16, 600u16, 1368u16, 344u16, 792u16, 1372u16, 0u16, 0u16,
+                    39u16, 1300u16, 276u16, 1044u16, 788u16, 0u16, 0u16, 38u16, 36u16, 31u16,
+                    264u16, 776u16, 524u16, 1292u16, 268u16, 780u16, 0u16, 1041u16, 530u16,
+                    1298u16, 274u16, 1042u16, 786u16, 0u16, 0u16, 52u16, 1304u16, 280u16, 792u16,
+                    1308u16, 0u16, 796u16,
+                ],
+                vec![(8364u16, 136u16)],
+                vec![
+                    DeadKey::new(Accent::Circumflex, 256u16, 47u16),
+                    DeadKey::new(Accent::AcuteAccent, 1280u16, 180u16),
+                    DeadKey::new(Accent::GraveAccent, 512u16, 177u16),
+                    DeadKey::new(Accent::Diaeresis, 768u16, 111u16),
+                    DeadKey::new(Accent::Tilde, 1024u16, 184u16),
+                ],
             ),
         );
         m.insert(
@@ -278,20 +1012,26 @@ lazy_static! {
                     14u16, 15u16, 16u16, 17u16, 18u16, 19u16, 20u16, 21u16, 22u16, 23u16, 24u16,
                     25u16, 26u16, 27u16, 29u16, 28u16, 180u16, 164u16, 177u16, 1068u16, 42u16,
                 ],
-                DeadKeys::new(
-                    Some(1280u16),
-                    None,
-                    None,
-                    None,
-                    Some(768u16),
-                    Some(61488u16),
-                    Some(512u16),
-                    Some(61550u16),
-                    Some(256u16),
-                    Some(61486u16),
-                    Some(1024u16),
-                    Some(61614u16),
-                ),
+                vec![
+                    44u16, 0u16, 165u16, 113u16, 0u16, 0u16, 158u16, 162u16, 812u16, 0u16, 0u16,
+                    0u16, 163u16, 0u16, 0u16, 0u16, 161u16, 0u16, 0u16, 0u16, 1324u16, 0u16, 0u16,
+                    0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 580u16, 1348u16, 324u16,
+                    1092u16, 836u16, 0u16, 0u16, 0u16, 584u16, 1352u16, 328u16, 840u16, 588u16,
+                    1356u16, 332u16, 844u16, 0u16, 1105u16, 594u16, 1362u16, 338u16, 1106u16,
+                    850u16, 0u16, 0u16, 600u16, 1368u16, 344u16, 856u16, 1372u16, 0u16, 0u16,
+                    52u16, 1284u16, 260u16, 1028u16, 116u16, 0u16, 0u16, 97u16, 47u16, 51u16,
+                    264u16, 776u16, 524u16, 1292u16, 268u16, 780u16, 0u16, 1041u16, 530u16,
+                    1298u16, 274u16, 1042u16, 115u16, 0u16, 0u16, 536u16, 1304u16, 280u16, 111u16,
+                    1308u16, 0u16, 796u16,
+                ],
+                vec![(8364u16, 136u16)],
+                vec![
+                    DeadKey::new(Accent::Circumflex, 256u16, 46u16),
+                    DeadKey::new(Accent::AcuteAccent, 1280u16, 173u16),
+                    DeadKey::new(Accent::GraveAccent, 512u16, 110u16),
+                    DeadKey::new(Accent::Diaeresis, 768u16, 48u16),
+                    DeadKey::new(Accent::Tilde, 1024u16, 174u16),
+                ],
             ),
         );
         m.insert(
@@ -315,20 +1055,23 @@ lazy_static! {
                     24u16, 25u16, 26u16, 27u16, 29u16, 28u16, 164u16, 191u16, 167u16, 176u16,
                     42u16,
                 ],
-                DeadKeys::new(
-                    Some(1024u16),
-                    None,
-                    None,
-                    None,
-                    None,
-                    None,
-                    Some(1280u16),
-                    Some(61550u16),
-                    Some(768u16),
-                    Some(61493u16),
-                    None,
-                    None,
-                ),
+                vec![
+                    44u16, 0u16, 0u16, 0u16, 136u16, 0u16, 0u16, 96u16, 0u16, 0u16, 0u16, 0u16,
+                    0u16, 0u16, 0u16, 0u16, 117u16, 0u16, 159u16, 160u16, 0u16, 144u16, 0u16, 0u16,
+                    0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 1348u16, 1092u16, 836u16, 0u16,
+                    116u16, 0u16, 0u16, 0u16, 1352u16, 1096u16, 840u16, 0u16, 1356u16, 1100u16,
+                    844u16, 0u16, 0u16, 0u16, 1362u16, 1106u16, 850u16, 0u16, 115u16, 0u16, 0u16,
+                    1368u16, 1112u16, 856u16, 111u16, 1117u16, 0u16, 45u16, 1284u16, 1028u16,
+                    772u16, 0u16, 52u16, 0u16, 0u16, 0u16, 1288u16, 1032u16, 776u16, 0u16, 1292u16,
+                    1036u16, 780u16, 0u16, 0u16, 0u16, 1298u16, 1042u16, 786u16, 0u16, 51u16, 0u16,
+                    0u16, 1304u16, 1048u16, 792u16, 47u16, 1053u16, 0u16, 0u16,
+                ],
+                vec![(8364u16, 136u16)],
+                vec![
+                    DeadKey::new(Accent::Circumflex, 768u16, 53u16),
+                    DeadKey::new(Accent::AcuteAccent, 1024u16, 46u16),
+                    DeadKey::new(Accent::GraveAccent, 1280u16, 110u16),
+                ],
             ),
         );
         m.insert(
@@ -351,20 +1094,26 @@ lazy_static! {
                     14u16, 15u16, 16u16, 17u16, 18u16, 19u16, 20u16, 21u16, 22u16, 23u16, 24u16,
                     25u16, 26u16, 27u16, 29u16, 28u16, 165u16, 164u16, 166u16, 1324u16, 42u16,
                 ],
-                DeadKeys::new(
-                    Some(256u16),
-                    None,
-                    None,
-                    None,
-                    Some(1024u16),
-                    Some(61592u16),
-                    Some(512u16),
-                    Some(61550u16),
-                    Some(768u16),
-                    Some(61667u16),
-                    Some(1280u16),
-                    Some(61585u16),
-                ),
+                vec![
+                    44u16, 158u16, 161u16, 225u16, 136u16, 157u16, 0u16, 96u16, 1068u16, 138u16,
+                    139u16, 148u16, 0u16, 0u16, 149u16, 231u16, 239u16, 176u16, 0u16, 0u16, 300u16,
+                    144u16, 160u16, 230u16, 0u16, 0u16, 141u16, 212u16, 0u16, 0u16, 0u16, 173u16,
+                    580u16, 324u16, 836u16, 1348u16, 116u16, 196u16, 244u16, 198u16, 584u16,
+                    328u16, 840u16, 1096u16, 588u16, 332u16, 844u16, 1100u16, 0u16, 1361u16,
+                    594u16, 338u16, 850u16, 1362u16, 115u16, 0u16, 210u16, 600u16, 344u16, 856u16,
+                    111u16, 349u16, 0u16, 45u16, 516u16, 260u16, 772u16, 1284u16, 52u16, 132u16,
+                    180u16, 134u16, 520u16, 264u16, 776u16, 1032u16, 524u16, 268u16, 780u16,
+                    1036u16, 0u16, 1297u16, 530u16, 274u16, 786u16, 1298u16, 51u16, 0u16, 146u16,
+                    536u16, 280u16, 792u16, 47u16, 285u16, 0u16, 1053u16,
+                ],
+                vec![(8364u16, 136u16)],
+                vec![
+                    DeadKey::new(Accent::AcuteAccent, 256u16, 46u16),
+                    DeadKey::new(Accent::GraveAccent, 512u16, 110u16),
+                    DeadKey::new(Accent::Circumflex, 768u16, 227u16),
+                    DeadKey::new(Accent::Diaeresis, 1024u16, 152u16),
+                    DeadKey::new(Accent::Tilde, 1280u16, 145u16),
+                ],
             ),
         );
         m.insert(
@@ -387,20 +1136,26 @@ lazy_static! {
                     14u16, 15u16, 16u16, 17u16, 18u16, 19u16, 20u16, 21u16, 22u16, 23u16, 24u16,
                     25u16, 26u16, 27u16, 29u16, 28u16, 180u16, 164u16, 177u16, 1068u16, 42u16,
                 ],
-                DeadKeys::new(
-                    Some(1280u16),
-                    None,
-                    None,
-                    None,
-                    Some(768u16),
-                    Some(61488u16),
-                    Some(512u16),
-                    Some(61550u16),
-                    Some(256u16),
-                    Some(61486u16),
-                    Some(1024u16),
-                    Some(61614u16),
-                ),
+                vec![
+                    44u16, 0u16, 165u16, 113u16, 0u16, 0u16, 158u16, 162u16, 812u16, 0u16, 0u16,
+                    0u16, 163u16, 0u16, 0u16, 0u16, 161u16, 0u16, 0u16, 0u16, 1324u16, 0u16, 0u16,
+                    0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 580u16, 1348u16, 324u16,
+                    1092u16, 836u16, 0u16, 0u16, 0u16, 584u16, 1352u16, 328u16, 840u16, 588u16,
+                    1356u16, 332u16, 844u16, 0u16, 1105u16, 594u16, 1362u16, 338u16, 1106u16,
+                    850u16, 0u16, 0u16, 600u16, 1368u16, 344u16, 856u16, 1372u16, 0u16, 0u16,
+                    116u16, 1284u16, 260u16, 1028u16, 52u16, 0u16, 0u16, 97u16, 111u16, 115u16,
+                    264u16, 776u16, 524u16, 1292u16, 268u16, 780u16, 0u16, 1041u16, 530u16,
+                    1298u16, 274u16, 1042u16, 51u16, 0u16, 0u16, 536u16, 1304u16, 280u16, 47u16,
+                    1308u16, 0u16, 796u16,
+                ],
+                vec![(8364u16, 136u16)],
+                vec![
+                    DeadKey::new(Accent::Circumflex, 256u16, 46u16),
+                    DeadKey::new(Accent::AcuteAccent, 1280u16, 173u16),
+                    DeadKey::new(Accent::GraveAccent, 512u16, 110u16),
+                    DeadKey::new(Accent::Diaeresis, 768u16, 48u16),
+                    DeadKey::new(Accent::Tilde, 1024u16, 174u16),
+                ],
             ),
         );
         m.insert(
@@ -423,20 +1178,26 @@ lazy_static! {
                     14u16, 15u16, 16u16, 17u16, 18u16, 19u16, 20u16, 21u16, 22u16, 23u16, 24u16,
                     25u16, 26u16, 27u16, 28u16, 29u16, 164u16, 191u16, 167u16, 176u16, 42u16,
                 ],
-                DeadKeys::new(
-                    Some(512u16),
-                    None,
-                    None,
-                    None,
-                    Some(1024u16),
-                    Some(61557u16),
-                    Some(768u16),
-                    Some(61617u16),
-                    Some(256u16),
-                    Some(61620u16),
-                    None,
-                    None,
-                ),
+                vec![
+                    44u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 1068u16, 0u16, 0u16, 0u16,
+                    0u16, 0u16, 0u16, 0u16, 181u16, 0u16, 0u16, 0u16, 556u16, 144u16, 0u16, 0u16,
+                    0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 836u16, 644u16, 324u16, 0u16,
+                    1092u16, 1348u16, 115u16, 0u16, 840u16, 584u16, 328u16, 1096u16, 844u16,
+                    588u16, 332u16, 1100u16, 111u16, 0u16, 850u16, 594u16, 338u16, 0u16, 1106u16,
+                    0u16, 0u16, 856u16, 600u16, 344u16, 1112u16, 604u16, 120u16, 0u16, 772u16,
+                    516u16, 260u16, 0u16, 1028u16, 1284u16, 51u16, 0u16, 776u16, 520u16, 264u16,
+                    1032u16, 780u16, 524u16, 268u16, 1036u16, 47u16, 0u16, 786u16, 530u16, 274u16,
+                    0u16, 1042u16, 0u16, 0u16, 792u16, 536u16, 280u16, 1048u16, 540u16, 56u16,
+                    1052u16,
+                ],
+                vec![(8364u16, 162u16)],
+                vec![
+                    DeadKey::new(Accent::Circumflex, 256u16, 180u16),
+                    DeadKey::new(Accent::AcuteAccent, 512u16, 52u16),
+                    DeadKey::new(Accent::GraveAccent, 768u16, 177u16),
+                    DeadKey::new(Accent::Diaeresis, 1024u16, 117u16),
+                    DeadKey::new(Accent::RingAbove, 1280u16, 53u16),
+                ],
             ),
         );
         m.insert(
@@ -459,20 +1220,21 @@ lazy_static! {
                     15u16, 16u16, 17u16, 18u16, 19u16, 20u16, 21u16, 22u16, 23u16, 24u16, 25u16,
                     26u16, 27u16, 28u16, 29u16, 111u16, 127u16, 112u16, 113u16, 42u16,
                 ],
-                DeadKeys::new(
-                    Some(512u16),
-                    None,
-                    None,
-                    None,
-                    None,
-                    None,
-                    Some(256u16),
-                    Some(61493u16),
-                    None,
-                    None,
-                    None,
-                    None,
-                ),
+                vec![
+                    44u16, 0u16, 0u16, 96u16, 0u16, 0u16, 181u16, 0u16, 0u16, 0u16, 0u16, 0u16,
+                    117u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16,
+                    0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 260u16, 196u16, 0u16, 0u16, 0u16,
+                    0u16, 0u16, 0u16, 264u16, 200u16, 0u16, 0u16, 268u16, 204u16, 0u16, 0u16, 0u16,
+                    0u16, 274u16, 210u16, 0u16, 0u16, 0u16, 0u16, 0u16, 280u16, 216u16, 0u16, 0u16,
+                    604u16, 0u16, 0u16, 260u16, 132u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 264u16,
+                    136u16, 0u16, 0u16, 268u16, 140u16, 0u16, 0u16, 0u16, 0u16, 274u16, 146u16,
+                    0u16, 0u16, 0u16, 0u16, 0u16, 280u16, 152u16, 0u16, 0u16, 540u16, 0u16, 0u16,
+                ],
+                vec![(8364u16, 161u16)],
+                vec![
+                    DeadKey::new(Accent::AcuteAccent, 512u16, 180u16),
+                    DeadKey::new(Accent::GraveAccent, 256u16, 53u16),
+                ],
             ),
         );
         m.insert(
@@ -495,9 +1257,18 @@ lazy_static! {
                     14u16, 15u16, 16u16, 17u16, 18u16, 19u16, 20u16, 21u16, 22u16, 23u16, 24u16,
                     25u16, 26u16, 27u16, 28u16, 29u16, 239u16, 117u16, 240u16, 0u16, 42u16,
                 ],
-                DeadKeys::new(
-                    None, None, None, None, None, None, None, None, None, None, None, None,
-                ),
+                vec![
+                    44u16, 0u16, 0u16, 96u16, 0u16, 0u16, 0u16, 113u16, 0u16, 0u16, 0u16, 0u16,
+                    0u16, 0u16, 0u16, 0u16, 116u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16,
+                    0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16,
+                    0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16,
+                    0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16,
+                    52u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 115u16, 47u16, 111u16, 0u16, 0u16,
+                    46u16, 0u16, 0u16, 0u16, 0u16, 0u16, 51u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16,
+                    49u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16,
+                ],
+                vec![],
+                vec![],
             ),
         );
         m.insert(
@@ -520,20 +1291,26 @@ lazy_static! {
                     14u16, 15u16, 16u16, 17u16, 18u16, 19u16, 20u16, 21u16, 22u16, 23u16, 24u16,
                     25u16, 26u16, 27u16, 28u16, 29u16, 164u16, 53u16, 167u16, 1068u16, 42u16,
                 ],
-                DeadKeys::new(
-                    Some(512u16),
-                    None,
-                    None,
-                    None,
-                    Some(1280u16),
-                    Some(61488u16),
-                    Some(768u16),
-                    Some(61550u16),
-                    Some(256u16),
-                    Some(61552u16),
-                    Some(1024u16),
-                    Some(61616u16),
-                ),
+                vec![
+                    44u16, 0u16, 0u16, 160u16, 97u16, 0u16, 0u16, 117u16, 1324u16, 0u16, 0u16,
+                    33u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 556u16, 144u16, 0u16,
+                    0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 53u16, 0u16, 0u16, 836u16, 580u16, 324u16,
+                    1092u16, 1348u16, 111u16, 116u16, 0u16, 840u16, 584u16, 328u16, 1352u16,
+                    844u16, 588u16, 332u16, 1356u16, 199u16, 1105u16, 850u16, 594u16, 338u16,
+                    1106u16, 1362u16, 0u16, 115u16, 856u16, 600u16, 344u16, 1368u16, 604u16,
+                    215u16, 150u16, 772u16, 516u16, 260u16, 1028u16, 1284u16, 47u16, 52u16, 0u16,
+                    776u16, 520u16, 264u16, 1288u16, 780u16, 524u16, 268u16, 1292u16, 135u16,
+                    1041u16, 786u16, 530u16, 274u16, 1042u16, 1298u16, 0u16, 51u16, 792u16, 536u16,
+                    280u16, 1304u16, 540u16, 151u16, 1308u16,
+                ],
+                vec![(8364u16, 136u16)],
+                vec![
+                    DeadKey::new(Accent::Circumflex, 256u16, 112u16),
+                    DeadKey::new(Accent::AcuteAccent, 512u16, 174u16),
+                    DeadKey::new(Accent::GraveAccent, 768u16, 110u16),
+                    DeadKey::new(Accent::Tilde, 1024u16, 176u16),
+                    DeadKey::new(Accent::Diaeresis, 1280u16, 48u16),
+                ],
             ),
         );
         m.insert(
@@ -556,20 +1333,26 @@ lazy_static! {
                     15u16, 16u16, 17u16, 18u16, 19u16, 20u16, 21u16, 22u16, 23u16, 24u16, 25u16,
                     26u16, 27u16, 28u16, 29u16, 164u16, 117u16, 167u16, 1068u16, 42u16,
                 ],
-                DeadKeys::new(
-                    Some(512u16),
-                    None,
-                    None,
-                    None,
-                    Some(1280u16),
-                    Some(61551u16),
-                    Some(768u16),
-                    Some(61552u16),
-                    Some(256u16),
-                    Some(61553u16),
-                    Some(1024u16),
-                    Some(61489u16),
-                ),
+                vec![
+                    44u16, 0u16, 0u16, 160u16, 0u16, 0u16, 0u16, 161u16, 1324u16, 0u16, 116u16,
+                    46u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 556u16, 0u16, 0u16,
+                    0u16, 0u16, 0u16, 52u16, 110u16, 0u16, 0u16, 0u16, 0u16, 836u16, 580u16,
+                    324u16, 1092u16, 1348u16, 0u16, 0u16, 115u16, 840u16, 584u16, 328u16, 1352u16,
+                    844u16, 588u16, 332u16, 1356u16, 0u16, 1105u16, 850u16, 594u16, 338u16,
+                    1106u16, 1362u16, 0u16, 0u16, 856u16, 600u16, 344u16, 1368u16, 604u16, 0u16,
+                    0u16, 772u16, 516u16, 260u16, 1028u16, 1284u16, 0u16, 0u16, 51u16, 776u16,
+                    520u16, 264u16, 1288u16, 780u16, 524u16, 268u16, 1292u16, 0u16, 1041u16,
+                    786u16, 530u16, 274u16, 1042u16, 1298u16, 0u16, 0u16, 792u16, 536u16, 280u16,
+                    1304u16, 540u16, 0u16, 1308u16,
+                ],
+                vec![(8364u16, 136u16)],
+                vec![
+                    DeadKey::new(Accent::Circumflex, 256u16, 113u16),
+                    DeadKey::new(Accent::AcuteAccent, 512u16, 112u16),
+                    DeadKey::new(Accent::GraveAccent, 768u16, 112u16),
+                    DeadKey::new(Accent::Tilde, 1024u16, 49u16),
+                    DeadKey::new(Accent::Diaeresis, 1280u16, 111u16),
+                ],
             ),
         );
         m.insert(
@@ -592,20 +1375,26 @@ lazy_static! {
                     15u16, 16u16, 17u16, 18u16, 19u16, 20u16, 21u16, 22u16, 23u16, 24u16, 25u16,
                     26u16, 27u16, 28u16, 29u16, 112u16, 127u16, 113u16, 1068u16, 42u16,
                 ],
-                DeadKeys::new(
-                    Some(512u16),
-                    None,
-                    None,
-                    None,
-                    Some(1280u16),
-                    Some(61539u16),
-                    Some(768u16),
-                    Some(61551u16),
-                    Some(256u16),
-                    Some(61556u16),
-                    Some(1024u16),
-                    Some(61492u16),
-                ),
+                vec![
+                    44u16, 0u16, 162u16, 161u16, 0u16, 0u16, 0u16, 174u16, 1324u16, 0u16, 176u16,
+                    0u16, 163u16, 0u16, 0u16, 0u16, 136u16, 0u16, 159u16, 159u16, 556u16, 0u16,
+                    0u16, 0u16, 0u16, 158u16, 177u16, 0u16, 0u16, 0u16, 0u16, 0u16, 836u16, 580u16,
+                    324u16, 1092u16, 1348u16, 0u16, 0u16, 0u16, 840u16, 584u16, 328u16, 1352u16,
+                    844u16, 588u16, 332u16, 1356u16, 0u16, 1105u16, 850u16, 594u16, 338u16,
+                    1106u16, 1362u16, 0u16, 0u16, 856u16, 600u16, 344u16, 1368u16, 604u16, 0u16,
+                    0u16, 772u16, 516u16, 260u16, 1028u16, 1284u16, 0u16, 0u16, 0u16, 776u16,
+                    520u16, 264u16, 1288u16, 780u16, 524u16, 268u16, 1292u16, 0u16, 1041u16,
+                    786u16, 530u16, 274u16, 1042u16, 1298u16, 0u16, 0u16, 792u16, 536u16, 280u16,
+                    1304u16, 540u16, 0u16, 1308u16,
+                ],
+                vec![],
+                vec![
+                    DeadKey::new(Accent::Circumflex, 256u16, 116u16),
+                    DeadKey::new(Accent::AcuteAccent, 512u16, 47u16),
+                    DeadKey::new(Accent::GraveAccent, 768u16, 111u16),
+                    DeadKey::new(Accent::Tilde, 1024u16, 52u16),
+                    DeadKey::new(Accent::Diaeresis, 1280u16, 99u16),
+                ],
             ),
         );
         m.insert(
@@ -628,20 +1417,26 @@ lazy_static! {
                     14u16, 15u16, 16u16, 17u16, 18u16, 19u16, 20u16, 21u16, 22u16, 23u16, 24u16,
                     25u16, 26u16, 27u16, 28u16, 29u16, 180u16, 158u16, 177u16, 1068u16, 42u16,
                 ],
-                DeadKeys::new(
-                    Some(512u16),
-                    None,
-                    None,
-                    None,
-                    Some(1280u16),
-                    Some(61556u16),
-                    Some(768u16),
-                    Some(61487u16),
-                    Some(256u16),
-                    Some(61551u16),
-                    Some(1024u16),
-                    Some(61601u16),
-                ),
+                vec![
+                    44u16, 46u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 1324u16, 0u16, 117u16, 0u16,
+                    163u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 556u16, 0u16, 0u16, 96u16,
+                    0u16, 0u16, 53u16, 0u16, 0u16, 0u16, 0u16, 46u16, 836u16, 580u16, 324u16,
+                    1092u16, 1348u16, 0u16, 0u16, 113u16, 840u16, 584u16, 328u16, 1352u16, 844u16,
+                    588u16, 332u16, 1356u16, 0u16, 115u16, 850u16, 594u16, 338u16, 1106u16,
+                    1362u16, 0u16, 0u16, 856u16, 600u16, 344u16, 1368u16, 604u16, 0u16, 0u16,
+                    772u16, 516u16, 260u16, 1028u16, 1284u16, 0u16, 0u16, 49u16, 776u16, 520u16,
+                    264u16, 1288u16, 780u16, 524u16, 268u16, 1292u16, 0u16, 51u16, 786u16, 530u16,
+                    274u16, 1042u16, 1298u16, 0u16, 0u16, 792u16, 536u16, 280u16, 1304u16, 540u16,
+                    0u16, 1308u16,
+                ],
+                vec![(8364u16, 162u16)],
+                vec![
+                    DeadKey::new(Accent::Circumflex, 256u16, 111u16),
+                    DeadKey::new(Accent::AcuteAccent, 512u16, 52u16),
+                    DeadKey::new(Accent::GraveAccent, 768u16, 47u16),
+                    DeadKey::new(Accent::Tilde, 1024u16, 161u16),
+                    DeadKey::new(Accent::Diaeresis, 1280u16, 116u16),
+                ],
             ),
         );
         m.insert(
@@ -664,20 +1459,24 @@ lazy_static! {
                     14u16, 15u16, 16u16, 17u16, 18u16, 19u16, 20u16, 21u16, 22u16, 23u16, 24u16,
                     25u16, 26u16, 27u16, 28u16, 29u16, 52u16, 53u16, 49u16, 176u16, 42u16,
                 ],
-                DeadKeys::new(
-                    Some(512u16),
-                    None,
-                    None,
-                    None,
-                    Some(1024u16),
-                    Some(61551u16),
-                    Some(768u16),
-                    Some(61617u16),
-                    Some(256u16),
-                    Some(61620u16),
-                    None,
-                    None,
-                ),
+                vec![
+                    44u16, 110u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 1068u16, 0u16, 0u16, 0u16,
+                    181u16, 0u16, 0u16, 0u16, 117u16, 0u16, 0u16, 0u16, 556u16, 0u16, 0u16, 0u16,
+                    0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 46u16, 836u16, 580u16, 324u16, 0u16,
+                    1092u16, 0u16, 0u16, 0u16, 840u16, 584u16, 328u16, 1096u16, 844u16, 588u16,
+                    332u16, 1100u16, 0u16, 115u16, 850u16, 594u16, 338u16, 0u16, 1106u16, 0u16,
+                    0u16, 856u16, 600u16, 344u16, 1112u16, 604u16, 0u16, 0u16, 772u16, 516u16,
+                    260u16, 0u16, 1028u16, 0u16, 0u16, 0u16, 776u16, 520u16, 264u16, 1032u16,
+                    780u16, 524u16, 268u16, 1036u16, 0u16, 51u16, 786u16, 530u16, 274u16, 0u16,
+                    1042u16, 0u16, 0u16, 792u16, 536u16, 280u16, 1048u16, 540u16, 0u16, 1052u16,
+                ],
+                vec![],
+                vec![
+                    DeadKey::new(Accent::Circumflex, 256u16, 180u16),
+                    DeadKey::new(Accent::AcuteAccent, 512u16, 47u16),
+                    DeadKey::new(Accent::GraveAccent, 768u16, 177u16),
+                    DeadKey::new(Accent::Diaeresis, 1024u16, 111u16),
+                ],
             ),
         );
         m.insert(
@@ -700,20 +1499,26 @@ lazy_static! {
                     14u16, 15u16, 16u16, 17u16, 18u16, 19u16, 20u16, 21u16, 22u16, 23u16, 24u16,
                     25u16, 26u16, 27u16, 28u16, 29u16, 164u16, 191u16, 167u16, 1068u16, 42u16,
                 ],
-                DeadKeys::new(
-                    Some(512u16),
-                    None,
-                    None,
-                    None,
-                    Some(1280u16),
-                    Some(61488u16),
-                    Some(768u16),
-                    Some(61550u16),
-                    Some(256u16),
-                    Some(61552u16),
-                    Some(1024u16),
-                    Some(61616u16),
-                ),
+                vec![
+                    44u16, 0u16, 0u16, 160u16, 97u16, 0u16, 0u16, 53u16, 1324u16, 0u16, 0u16,
+                    33u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 556u16, 144u16, 0u16,
+                    0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 117u16, 0u16, 0u16, 836u16, 580u16, 324u16,
+                    1092u16, 116u16, 111u16, 0u16, 0u16, 840u16, 584u16, 328u16, 1352u16, 844u16,
+                    588u16, 332u16, 1356u16, 199u16, 1105u16, 850u16, 594u16, 338u16, 1106u16,
+                    115u16, 0u16, 0u16, 856u16, 600u16, 344u16, 1368u16, 604u16, 215u16, 150u16,
+                    772u16, 516u16, 260u16, 1028u16, 52u16, 47u16, 0u16, 0u16, 776u16, 520u16,
+                    264u16, 1288u16, 780u16, 524u16, 268u16, 1292u16, 135u16, 1041u16, 786u16,
+                    530u16, 274u16, 1042u16, 51u16, 0u16, 0u16, 792u16, 536u16, 280u16, 1304u16,
+                    540u16, 151u16, 1308u16,
+                ],
+                vec![(8364u16, 136u16)],
+                vec![
+                    DeadKey::new(Accent::Circumflex, 256u16, 112u16),
+                    DeadKey::new(Accent::AcuteAccent, 512u16, 46u16),
+                    DeadKey::new(Accent::GraveAccent, 768u16, 110u16),
+                    DeadKey::new(Accent::Tilde, 1024u16, 176u16),
+                    DeadKey::new(Accent::Diaeresis, 1280u16, 48u16),
+                ],
             ),
         );
         m.insert(
@@ -736,20 +1541,34 @@ lazy_static! {
                     14u16, 15u16, 16u16, 17u16, 18u16, 19u16, 20u16, 21u16, 22u16, 23u16, 24u16,
                     25u16, 26u16, 27u16, 28u16, 29u16, 164u16, 174u16, 167u16, 1068u16, 42u16,
                 ],
-                DeadKeys::new(
-                    Some(512u16),
-                    None,
-                    None,
-                    None,
-                    Some(1280u16),
-                    Some(61615u16),
-                    Some(768u16),
-                    Some(61617u16),
-                    Some(256u16),
-                    Some(61536u16),
-                    Some(1024u16),
-                    Some(61616u16),
-                ),
+                vec![
+                    44u16, 0u16, 0u16, 159u16, 0u16, 0u16, 0u16, 0u16, 1324u16, 0u16, 0u16, 0u16,
+                    0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 556u16, 0u16, 0u16, 0u16, 0u16,
+                    0u16, 0u16, 0u16, 0u16, 162u16, 0u16, 0u16, 836u16, 580u16, 324u16, 1092u16,
+                    1348u16, 0u16, 132u16, 119u16, 840u16, 584u16, 328u16, 1352u16, 844u16, 588u16,
+                    332u16, 1356u16, 0u16, 1105u16, 850u16, 594u16, 338u16, 1106u16, 118u16, 0u16,
+                    0u16, 856u16, 600u16, 344u16, 112u16, 604u16, 0u16, 150u16, 772u16, 516u16,
+                    260u16, 1028u16, 1284u16, 0u16, 0u16, 55u16, 776u16, 117u16, 264u16, 1288u16,
+                    780u16, 524u16, 268u16, 1292u16, 0u16, 1041u16, 786u16, 530u16, 274u16,
+                    1042u16, 54u16, 0u16, 0u16, 792u16, 536u16, 280u16, 48u16, 540u16, 0u16,
+                    1308u16,
+                ],
+                vec![
+                    (8364u16, 136u16),
+                    (286u16, 111u16),
+                    (287u16, 47u16),
+                    (304u16, 116u16),
+                    (305u16, 12u16),
+                    (350u16, 115u16),
+                    (337u16, 51u16),
+                ],
+                vec![
+                    DeadKey::new(Accent::Circumflex, 256u16, 96u16),
+                    DeadKey::new(Accent::AcuteAccent, 512u16, 179u16),
+                    DeadKey::new(Accent::GraveAccent, 768u16, 177u16),
+                    DeadKey::new(Accent::Tilde, 1024u16, 176u16),
+                    DeadKey::new(Accent::Diaeresis, 1280u16, 175u16),
+                ],
             ),
         );
         m.insert(
@@ -772,9 +1591,18 @@ lazy_static! {
                     15u16, 16u16, 17u16, 18u16, 19u16, 20u16, 21u16, 22u16, 23u16, 24u16, 25u16,
                     26u16, 27u16, 28u16, 29u16, 111u16, 127u16, 112u16, 113u16, 42u16,
                 ],
-                DeadKeys::new(
-                    None, None, None, None, None, None, None, None, None, None, None, None,
-                ),
+                vec![
+                    44u16, 0u16, 0u16, 96u16, 0u16, 0u16, 181u16, 0u16, 0u16, 0u16, 0u16, 0u16,
+                    117u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16,
+                    0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 196u16, 0u16, 0u16, 0u16, 0u16,
+                    0u16, 0u16, 0u16, 200u16, 0u16, 0u16, 0u16, 204u16, 0u16, 0u16, 0u16, 0u16,
+                    0u16, 210u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 216u16, 0u16, 0u16, 0u16,
+                    0u16, 0u16, 0u16, 132u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 136u16,
+                    0u16, 0u16, 0u16, 140u16, 0u16, 0u16, 0u16, 0u16, 0u16, 146u16, 0u16, 0u16,
+                    0u16, 0u16, 0u16, 0u16, 152u16, 0u16, 0u16, 0u16, 0u16, 0u16,
+                ],
+                vec![(8364u16, 161u16)],
+                vec![],
             ),
         );
         m.insert(
@@ -797,20 +1625,27 @@ lazy_static! {
                     15u16, 16u16, 17u16, 18u16, 19u16, 20u16, 21u16, 22u16, 23u16, 24u16, 25u16,
                     26u16, 27u16, 28u16, 29u16, 111u16, 113u16, 112u16, 1068u16, 42u16,
                 ],
-                DeadKeys::new(
-                    Some(512u16),
-                    None,
-                    None,
-                    None,
-                    Some(1280u16),
-                    Some(61556u16),
-                    Some(768u16),
-                    Some(61493u16),
-                    Some(256u16),
-                    Some(61539u16),
-                    Some(1024u16),
-                    Some(61557u16),
-                ),
+                vec![
+                    44u16, 158u16, 198u16, 225u16, 161u16, 173u16, 241u16, 214u16, 244u16, 134u16,
+                    0u16, 175u16, 177u16, 0u16, 149u16, 0u16, 243u16, 0u16, 159u16, 160u16, 180u16,
+                    144u16, 179u16, 0u16, 0u16, 222u16, 0u16, 176u16, 163u16, 164u16, 165u16,
+                    184u16, 836u16, 196u16, 324u16, 1092u16, 212u16, 218u16, 221u16, 246u16,
+                    840u16, 200u16, 328u16, 1352u16, 844u16, 204u16, 332u16, 1356u16, 199u16,
+                    209u16, 850u16, 210u16, 338u16, 1106u16, 211u16, 174u16, 207u16, 856u16,
+                    216u16, 344u16, 220u16, 604u16, 215u16, 150u16, 772u16, 132u16, 260u16,
+                    1028u16, 1284u16, 154u16, 157u16, 182u16, 776u16, 520u16, 264u16, 1288u16,
+                    780u16, 140u16, 268u16, 1292u16, 135u16, 145u16, 786u16, 146u16, 274u16,
+                    1042u16, 147u16, 238u16, 143u16, 792u16, 152u16, 280u16, 156u16, 540u16,
+                    151u16, 1308u16,
+                ],
+                vec![(8364u16, 162u16)],
+                vec![
+                    DeadKey::new(Accent::Circumflex, 256u16, 99u16),
+                    DeadKey::new(Accent::AcuteAccent, 512u16, 52u16),
+                    DeadKey::new(Accent::GraveAccent, 768u16, 53u16),
+                    DeadKey::new(Accent::Tilde, 1024u16, 117u16),
+                    DeadKey::new(Accent::Diaeresis, 1280u16, 116u16),
+                ],
             ),
         );
         m
@@ -0,0 +1,54 @@
+use crate::Key;
+
+use std::fmt;
+use std::str::FromStr;
+
+const KEY_PREFIX: &str = "KEY_";
+
+/// A key name that has no matching `Key`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidKey(pub String);
+
+impl Key {
+    /// The HID usage ID sent in a keyboard report
+    pub fn usage(self) -> u8 {
+        self.keycode() as u8
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// Accepts the header name with or without the KEY_ prefix in any case, e.g. "KEY_PAGE_UP",
+// "page_up" or "F5", plus the usual spellings of some keys, e.g. "Del", "Escape" or "PageUp"
+impl FromStr for Key {
+    type Err = InvalidKey;
+
+    fn from_str(s: &str) -> Result<Key, InvalidKey> {
+        let name = s.to_uppercase();
+        match name.as_str() {
+            "DEL" => Ok(Key::Delete),
+            "ESCAPE" => Ok(Key::Esc),
+            "RETURN" => Ok(Key::Enter),
+            "PAGEUP" => Ok(Key::PageUp),
+            "PAGEDOWN" => Ok(Key::PageDown),
+            "CAPSLOCK" => Ok(Key::CapsLock),
+            "NUMLOCK" => Ok(Key::NumLock),
+            "SCROLLLOCK" => Ok(Key::ScrollLock),
+            _ => Key::ALL
+                .iter()
+                .find(|key| key.name() == name || key.name().trim_start_matches(KEY_PREFIX) == name)
+                .copied()
+                .ok_or_else(|| InvalidKey(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for InvalidKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unknown key: {}", self.0)
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{Layout, LayoutId, LAYOUT_MAP};
+use lazy_static::lazy_static;
+
+use std::fmt;
+use std::str::FromStr;
+
+lazy_static! {
+    // The layout of each id in `LayoutId::ALL` order, which is the order of the variants
+    static ref LAYOUTS: Vec<&'static Layout> = LayoutId::ALL
+        .iter()
+        .map(|id| &LAYOUT_MAP[id.name()])
+        .collect();
+}
+
+/// A layout name that has no matching `LayoutId`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidLayoutId(pub String);
+
+impl LayoutId {
+    /// The layout definition, resolved once so it can be reused without a lookup by name
+    pub fn layout(self) -> &'static Layout {
+        LAYOUTS[self as usize]
+    }
+}
+
+impl fmt::Display for LayoutId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for LayoutId {
+    type Err = InvalidLayoutId;
+
+    fn from_str(s: &str) -> Result<LayoutId, InvalidLayoutId> {
+        LayoutId::ALL
+            .iter()
+            .find(|id| id.name() == s)
+            .copied()
+            .ok_or_else(|| InvalidLayoutId(s.to_string()))
+    }
+}
+
+impl fmt::Display for InvalidLayoutId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "No layout defined for {}", self.0)
+    }
+}
@@ -1,9 +1,19 @@
 #[cfg(not(feature = "generate"))]
 mod generated;
+mod key;
+mod layout_id;
+mod media_key;
+mod modifier;
+mod system_key;
 mod types;
 
 #[cfg(not(feature = "generate"))]
 pub use generated::*;
+pub use key::*;
+pub use layout_id::*;
+pub use media_key::*;
+pub use modifier::*;
+pub use system_key::*;
 pub use types::*;
 
 // See build script - Provides:
@@ -12,6 +22,11 @@ pub use types::*;
 // SHIFT_MODIFIER: u16
 // RIGHT_ALT_MODIFIER: u16
 // RIGHT_CTRL_MODIFIER: u16
+// Key: enum with a variant per named key, e.g. Key::PageUp
+// MediaKey: enum with a variant per consumer control key, e.g. MediaKey::VolumeInc
+// Modifier: enum with a variant per modifier key, e.g. Modifier::LeftGui
+// LayoutId: enum with a variant per layout, e.g. LayoutId::UsEnglish
+// SystemKey: enum with a variant per system control key, e.g. SystemKey::Sleep
 // LAYOUT_MAP: HashMap<&'static str, Layout>
 #[cfg(feature = "generate")]
 include!(concat!(env!("OUT_DIR"), "/generated.rs"));
@@ -0,0 +1,49 @@
+use crate::MediaKey;
+
+use std::fmt;
+use std::str::FromStr;
+
+const MEDIA_KEY_PREFIX: &str = "KEY_MEDIA_";
+// The header tags consumer page usages with 0xE400
+const MEDIA_USAGE_MASK: u16 = 0x03FF;
+
+/// A media key name that has no matching `MediaKey`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidMediaKey(pub String);
+
+impl MediaKey {
+    /// The HID consumer page usage ID sent in a consumer control report
+    pub fn usage(self) -> u16 {
+        self.keycode() & MEDIA_USAGE_MASK
+    }
+}
+
+impl fmt::Display for MediaKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// Accepts the header name with or without the KEY_MEDIA_ prefix in any case, e.g.
+// "KEY_MEDIA_MUTE" or "volume_inc"
+impl FromStr for MediaKey {
+    type Err = InvalidMediaKey;
+
+    fn from_str(s: &str) -> Result<MediaKey, InvalidMediaKey> {
+        let name = s.to_uppercase();
+        MediaKey::ALL
+            .iter()
+            .find(|media_key| {
+                media_key.name() == name
+                    || media_key.name().trim_start_matches(MEDIA_KEY_PREFIX) == name
+            })
+            .copied()
+            .ok_or_else(|| InvalidMediaKey(s.to_string()))
+    }
+}
+
+impl fmt::Display for InvalidMediaKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unknown media key: {}", self.0)
+    }
+}
@@ -0,0 +1,53 @@
+use crate::Modifier;
+
+use std::fmt;
+use std::str::FromStr;
+
+const MODIFIER_PREFIX: &str = "MODIFIERKEY_";
+
+/// A modifier name that has no matching `Modifier`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidModifier(pub String);
+
+impl Modifier {
+    /// The bit set in the modifier byte of a keyboard report
+    pub fn bits(self) -> u8 {
+        self.keycode() as u8
+    }
+}
+
+impl fmt::Display for Modifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// Accepts the header name with or without the MODIFIERKEY_ prefix in any case, plus the usual
+// names for the GUI, Ctrl and Alt keys on each platform, e.g. "Win", "Cmd" or "Option"
+impl FromStr for Modifier {
+    type Err = InvalidModifier;
+
+    fn from_str(s: &str) -> Result<Modifier, InvalidModifier> {
+        let name = s.to_uppercase();
+        match name.as_str() {
+            "WIN" | "WINDOWS" | "CMD" | "COMMAND" | "SUPER" | "META" => Ok(Modifier::Gui),
+            "CONTROL" => Ok(Modifier::Ctrl),
+            "OPTION" | "OPT" => Ok(Modifier::Alt),
+            "ALTGR" => Ok(Modifier::RightAlt),
+            _ => Modifier::ALL
+                .iter()
+                .find(|modifier| {
+                    modifier.name() == name
+                        || modifier.name().trim_start_matches(MODIFIER_PREFIX) == name
+                })
+                .copied()
+                .ok_or_else(|| InvalidModifier(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for InvalidModifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unknown modifier: {}", self.0)
+    }
+}
@@ -0,0 +1,49 @@
+use crate::SystemKey;
+
+use std::fmt;
+use std::str::FromStr;
+
+const SYSTEM_KEY_PREFIX: &str = "KEY_SYSTEM_";
+// The header tags generic desktop system control usages with 0xE200
+const SYSTEM_USAGE_MASK: u16 = 0x00FF;
+
+/// A system key name that has no matching `SystemKey`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidSystemKey(pub String);
+
+impl SystemKey {
+    /// The HID generic desktop usage ID sent in a system control report
+    pub fn usage(self) -> u8 {
+        (self.keycode() & SYSTEM_USAGE_MASK) as u8
+    }
+}
+
+impl fmt::Display for SystemKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// Accepts the header name with or without the KEY_SYSTEM_ prefix in any case, e.g.
+// "KEY_SYSTEM_SLEEP" or "wake_up"
+impl FromStr for SystemKey {
+    type Err = InvalidSystemKey;
+
+    fn from_str(s: &str) -> Result<SystemKey, InvalidSystemKey> {
+        let name = s.to_uppercase();
+        SystemKey::ALL
+            .iter()
+            .find(|system_key| {
+                system_key.name() == name
+                    || system_key.name().trim_start_matches(SYSTEM_KEY_PREFIX) == name
+            })
+            .copied()
+            .ok_or_else(|| InvalidSystemKey(s.to_string()))
+    }
+}
+
+impl fmt::Display for InvalidSystemKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unknown system key: {}", self.0)
+    }
+}
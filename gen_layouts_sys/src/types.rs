@@ -1,57 +1,128 @@
-pub struct DeadKeys {
-    pub acute_accent_bits: Option<u16>,
-    pub deadkey_accute_accent: Option<u16>,
-    pub cedilla_bits: Option<u16>,
-    pub deadkey_cedilla: Option<u16>,
-    pub diaeresis_bits: Option<u16>,
-    pub deadkey_diaeresis: Option<u16>,
-    pub grave_accent_bits: Option<u16>,
-    pub deadkey_grave_accent: Option<u16>,
-    pub circumflex_bits: Option<u16>,
-    pub deadkey_circumflex: Option<u16>,
-    pub tilde_bits: Option<u16>,
-    pub deadkey_tilde: Option<u16>,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Accent {
+    AcuteAccent,
+    Breve,
+    Caron,
+    Cedilla,
+    Circumflex,
+    DegreeSign,
+    Diaeresis,
+    DotAbove,
+    DoubleAcute,
+    GraveAccent,
+    Ogonek,
+    RingAbove,
+    Tilde,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeadKey {
+    pub accent: Accent,
+    pub bits: u16,
+    pub keycode: u16,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Layout {
     pub shift_mask: u16,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub alt_mask: Option<u16>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub ctrl_mask: Option<u16>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub non_us: Option<u16>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub dead_keys_mask: Option<u16>,
     pub keycode_mask: u16,
     pub keycodes: Box<[u16]>,
-    pub deadkeys: DeadKeys,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub latin1_keycodes: Box<[u16]>,
+    // (unicode, keycode) pairs for characters outside ASCII and ISO-8859-1
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub extra_keycodes: Box<[(u16, u16)]>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub deadkeys: Box<[DeadKey]>,
 }
 
-impl DeadKeys {
-    pub fn new(
-        acute_accent_bits: Option<u16>,
-        deadkey_accute_accent: Option<u16>,
-        cedilla_bits: Option<u16>,
-        deadkey_cedilla: Option<u16>,
-        diaeresis_bits: Option<u16>,
-        deadkey_diaeresis: Option<u16>,
-        grave_accent_bits: Option<u16>,
-        deadkey_grave_accent: Option<u16>,
-        circumflex_bits: Option<u16>,
-        deadkey_circumflex: Option<u16>,
-        tilde_bits: Option<u16>,
-        deadkey_tilde: Option<u16>,
-    ) -> DeadKeys {
-        DeadKeys {
-            acute_accent_bits,
-            deadkey_accute_accent,
-            cedilla_bits,
-            deadkey_cedilla,
-            diaeresis_bits,
-            deadkey_diaeresis,
-            grave_accent_bits,
-            deadkey_grave_accent,
-            circumflex_bits,
-            deadkey_circumflex,
-            tilde_bits,
-            deadkey_tilde,
+impl Accent {
+    /// The Unicode combining character this accent adds to the following base character.
+    /// `DegreeSign` has no combining character of its own and shares `RingAbove`'s, so where
+    /// both could apply, e.g. composing 'å' on a layout with both deadkeys, `RingAbove` is used.
+    pub fn combining_char(self) -> char {
+        match self {
+            Accent::AcuteAccent => '\u{0301}',
+            Accent::Breve => '\u{0306}',
+            Accent::Caron => '\u{030C}',
+            Accent::Cedilla => '\u{0327}',
+            Accent::Circumflex => '\u{0302}',
+            Accent::DegreeSign => '\u{030A}',
+            Accent::Diaeresis => '\u{0308}',
+            Accent::DotAbove => '\u{0307}',
+            Accent::DoubleAcute => '\u{030B}',
+            Accent::GraveAccent => '\u{0300}',
+            Accent::Ogonek => '\u{0328}',
+            Accent::RingAbove => '\u{030A}',
+            Accent::Tilde => '\u{0303}',
+        }
+    }
+
+    /// The character typed by pressing the deadkey and then space
+    pub fn spacing_char(self) -> char {
+        match self {
+            Accent::AcuteAccent => '\u{00B4}',
+            Accent::Breve => '\u{02D8}',
+            Accent::Caron => '\u{02C7}',
+            Accent::Cedilla => '\u{00B8}',
+            Accent::Circumflex => '^',
+            Accent::DegreeSign => '\u{00B0}',
+            Accent::Diaeresis => '\u{00A8}',
+            Accent::DotAbove => '\u{02D9}',
+            Accent::DoubleAcute => '\u{02DD}',
+            Accent::GraveAccent => '`',
+            Accent::Ogonek => '\u{02DB}',
+            Accent::RingAbove => '\u{02DA}',
+            Accent::Tilde => '~',
+        }
+    }
+
+    /// The accent with the given spacing or combining character
+    pub fn from_char(c: char) -> Option<Accent> {
+        Accent::ALL
+            .iter()
+            .find(|accent| accent.spacing_char() == c)
+            .or_else(|| {
+                Accent::ALL
+                    .iter()
+                    .filter(|accent| **accent != Accent::DegreeSign)
+                    .find(|accent| accent.combining_char() == c)
+            })
+            .copied()
+    }
+
+    pub const ALL: [Accent; 13] = [
+        Accent::AcuteAccent,
+        Accent::Breve,
+        Accent::Caron,
+        Accent::Cedilla,
+        Accent::Circumflex,
+        Accent::DegreeSign,
+        Accent::Diaeresis,
+        Accent::DotAbove,
+        Accent::DoubleAcute,
+        Accent::GraveAccent,
+        Accent::Ogonek,
+        Accent::RingAbove,
+        Accent::Tilde,
+    ];
+}
+
+impl DeadKey {
+    pub fn new(accent: Accent, bits: u16, keycode: u16) -> DeadKey {
+        DeadKey {
+            accent,
+            bits,
+            keycode,
         }
     }
 }
@@ -65,9 +136,14 @@ impl Layout {
         dead_keys_mask: Option<u16>,
         keycode_mask: u16,
         keycodes: Vec<u16>,
-        deadkeys: DeadKeys,
+        latin1_keycodes: Vec<u16>,
+        extra_keycodes: Vec<(u16, u16)>,
+        deadkeys: Vec<DeadKey>,
     ) -> Layout {
         let keycodes = keycodes.into_boxed_slice();
+        let latin1_keycodes = latin1_keycodes.into_boxed_slice();
+        let extra_keycodes = extra_keycodes.into_boxed_slice();
+        let deadkeys = deadkeys.into_boxed_slice();
         Layout {
             shift_mask,
             alt_mask,
@@ -76,6 +152,8 @@ impl Layout {
             dead_keys_mask,
             keycode_mask,
             keycodes,
+            latin1_keycodes,
+            extra_keycodes,
             deadkeys,
         }
     }
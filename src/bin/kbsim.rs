@@ -1,7 +1,12 @@
+use bytes::Bytes;
+use keyboard_layouts::{
+    Device, EncodeOptions, Fallback, KeyRollover, LayoutExt, ReportFormat, Script, Step, Untypeable,
+};
 use structopt::StructOpt;
 
 use std::fs;
 use std::io::{Error, ErrorKind, Result};
+use std::path::Path;
 use std::thread;
 use std::time::Duration;
 
@@ -24,6 +29,30 @@ struct CliOpt {
         default_value = "LAYOUT_US_ENGLISH"
     )]
     layout: String,
+    #[cfg(feature = "layout-files")]
+    #[structopt(
+        long = "layout-file",
+        help = "Load a layout from a .toml or .json file, named after the file without its extension, e.g. 'my_layout' for my_layout.toml. Can be given more than once"
+    )]
+    layout_files: Vec<String>,
+    #[structopt(
+        long = "xkb",
+        number_of_values = 1,
+        help = "Load a layout from an XKB symbols file, optionally followed by a variant, e.g. '/usr/share/X11/xkb/symbols/ch(fr)', named 'ch(fr)'. Can be given more than once"
+    )]
+    xkb_files: Vec<String>,
+    #[structopt(
+        long = "klc",
+        number_of_values = 1,
+        help = "Load a layout from a Windows .klc file, named after the file without its extension. Can be given more than once"
+    )]
+    klc_files: Vec<String>,
+    #[structopt(
+        long = "keylayout",
+        number_of_values = 1,
+        help = "Load a layout from a macOS .keylayout file, named after the file without its extension. Can be given more than once"
+    )]
+    keylayout_files: Vec<String>,
     #[structopt(
         long = "newline",
         short = "n",
@@ -44,20 +73,181 @@ struct CliOpt {
         default_value = "0"
     )]
     cooldown: u64,
+    #[structopt(
+        long = "fallback",
+        short = "u",
+        help = "How to type characters the layout cannot: 'none', 'alt-numpad', 'alt-numpad-numlock', 'ctrl-shift-u' or 'unicode-hex-input'",
+        default_value = "none"
+    )]
+    fallback: Fallback,
+    #[structopt(
+        long = "untypeable",
+        short = "t",
+        help = "What to do with characters that still cannot be typed: 'error', 'skip', 'replace:<char>' or 'transliterate'",
+        default_value = "error"
+    )]
+    untypeable: Untypeable,
+    #[structopt(
+        long = "script",
+        short = "s",
+        help = "Run a keystroke script from the given file instead of writing a string"
+    )]
+    script: Option<String>,
+    #[structopt(long = "ducky", help = "Read the script as a DuckyScript payload")]
+    ducky: bool,
+    #[structopt(
+        long = "device",
+        help = "How the HID device is set up: 'keyboard' or 'composite' for a keyboard, consumer control (media keys) and system control (power, sleep and wake up) device",
+        default_value = "keyboard"
+    )]
+    device: Device,
+    #[structopt(
+        long = "format",
+        help = "How the keyboard reports lay out the pressed keys: 'boot' for 6 key rollover or 'nkro:<bytes>' for a key bitmap of the given size",
+        default_value = "boot"
+    )]
+    format: KeyRollover,
+    #[structopt(
+        long = "report-id",
+        help = "Prefix every keyboard report with the given report ID"
+    )]
+    report_id: Option<u8>,
+    #[structopt(
+        long = "packed",
+        help = "Press several keys per HID packet where the order they are typed in is kept, to write long strings faster"
+    )]
+    packed: bool,
+    #[structopt(
+        long = "decode",
+        help = "Print the text a file of captured 8 byte HID packets types with the layout instead of writing a string"
+    )]
+    decode: Option<String>,
+    #[structopt(
+        long = "keys",
+        help = "Print the characters on each key of the layout at each shift level"
+    )]
+    keys: bool,
     #[structopt(name = "STRING")]
     string: Option<String>,
+    #[cfg(feature = "layout-files")]
+    #[structopt(subcommand)]
+    command: Option<Command>,
+}
+
+#[cfg(feature = "layout-files")]
+#[derive(Debug, StructOpt)]
+enum Command {
+    #[structopt(name = "layouts", about = "Work with the layout definitions")]
+    Layouts(LayoutsCommand),
+}
+
+#[cfg(feature = "layout-files")]
+#[derive(Debug, StructOpt)]
+enum LayoutsCommand {
+    #[structopt(
+        name = "export",
+        about = "Print a layout's masks, keycodes and deadkeys in the format read by --layout-file"
+    )]
+    Export {
+        #[structopt(long = "json", help = "Print JSON instead of TOML")]
+        json: bool,
+        #[structopt(name = "LAYOUT")]
+        layout: String,
+    },
 }
 
 fn main() -> Result<()> {
     let CliOpt {
         hid_file,
         layout,
+        #[cfg(feature = "layout-files")]
+        layout_files,
+        xkb_files,
+        klc_files,
+        keylayout_files,
         newline,
         delay,
         cooldown,
+        fallback,
+        untypeable,
+        script,
+        ducky,
+        device,
+        format,
+        report_id,
+        packed,
+        decode,
+        keys,
         string,
+        #[cfg(feature = "layout-files")]
+        command,
     } = CliOpt::from_args();
 
+    #[cfg(feature = "layout-files")]
+    for layout_file in layout_files {
+        let layout_key = Path::new(&layout_file)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default();
+        keyboard_layouts::load_layout(&layout_file)
+            .and_then(|loaded| keyboard_layouts::register_layout(layout_key, loaded))
+            .map_err(|e| Error::new(ErrorKind::Other, format!("{}", e)))?;
+    }
+
+    for xkb_file in xkb_files {
+        let (path, variant) = match xkb_file.strip_suffix(')').and_then(|f| f.split_once('(')) {
+            Some((path, variant)) => (path, Some(variant)),
+            None => (xkb_file.as_str(), None),
+        };
+        let file_name = Path::new(path)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        let layout_key = match variant {
+            Some(variant) => format!("{}({})", file_name, variant),
+            None => file_name.to_string(),
+        };
+        keyboard_layouts::load_xkb_layout(path, variant)
+            .and_then(|loaded| keyboard_layouts::register_layout(&layout_key, loaded))
+            .map_err(|e| Error::new(ErrorKind::Other, format!("{}", e)))?;
+    }
+
+    for klc_file in klc_files {
+        let layout_key = Path::new(&klc_file)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default();
+        keyboard_layouts::load_klc_layout(&klc_file)
+            .and_then(|loaded| keyboard_layouts::register_layout(layout_key, loaded))
+            .map_err(|e| Error::new(ErrorKind::Other, format!("{}", e)))?;
+    }
+
+    for keylayout_file in keylayout_files {
+        let layout_key = Path::new(&keylayout_file)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default();
+        keyboard_layouts::load_keylayout(&keylayout_file)
+            .and_then(|loaded| keyboard_layouts::register_layout(layout_key, loaded))
+            .map_err(|e| Error::new(ErrorKind::Other, format!("{}", e)))?;
+    }
+
+    #[cfg(feature = "layout-files")]
+    if let Some(Command::Layouts(LayoutsCommand::Export { json, layout })) = command {
+        let exported = keyboard_layouts::get_layout(&layout).and_then(|layout| {
+            if json {
+                keyboard_layouts::layout_to_json(layout)
+            } else {
+                keyboard_layouts::layout_to_toml(layout)
+            }
+        });
+        println!(
+            "{}",
+            exported.map_err(|e| Error::new(ErrorKind::Other, format!("{}", e)))?
+        );
+        return Ok(());
+    }
+
     if layout.to_lowercase() == "list" {
         for l in keyboard_layouts::available_layouts() {
             println!("{}", l);
@@ -65,22 +255,120 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
-    if let Some(mut string) = string {
-        let hid_file = hid_file.unwrap_or_else(|| "/dev/hidg0".to_string());
+    if keys {
+        let layout = keyboard_layouts::get_layout(&layout)
+            .map_err(|e| Error::new(ErrorKind::Other, format!("{}", e)))?;
+        for key_chars in layout.all_key_chars() {
+            let key = key_chars.chord().map_or_else(
+                || format!("{:#04X}", key_chars.usage),
+                |chord| chord.to_string(),
+            );
+            let mut typed = key_chars
+                .chars
+                .iter()
+                .map(|c| format!("{:?}", c))
+                .collect::<Vec<String>>();
+            if let Some(accent) = key_chars.deadkey {
+                typed.push(format!("dead {:?}", accent));
+            }
+            println!("{}\t{}", key, typed.join(" "));
+        }
+        return Ok(());
+    }
 
-        if newline {
-            string.push('\n');
+    let device = match (device, format, report_id) {
+        (device, KeyRollover::Boot, None) => device,
+        (Device::Keyboard, rollover, report_id) => Device::Custom(ReportFormat {
+            rollover,
+            report_id,
+        }),
+        (device, _, _) => {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!(
+                    "--format and --report-id cannot be used with a {} device",
+                    device
+                ),
+            ))
         }
+    };
+
+    let hid_file = hid_file.unwrap_or_else(|| "/dev/hidg0".to_string());
+    let options = EncodeOptions {
+        fallback,
+        untypeable,
+    };
 
-        let hid_bytes = keyboard_layouts::string_to_hid_packets(&layout, &string)
+    if let Some(decode) = decode {
+        let packets = fs::read(decode)?;
+        let string = keyboard_layouts::hid_packets_to_string(&layout, &packets)
+            .map_err(|e| Error::new(ErrorKind::Other, format!("{}", e)))?;
+        print!("{}", string);
+        return Ok(());
+    }
+
+    if let Some(script) = script {
+        let script = fs::read_to_string(script)?;
+        let script = if ducky {
+            Script::parse_ducky(&script)
+        } else {
+            Script::parse(&script)
+        };
+        let steps = script
+            .and_then(|script| script.compile_for_device(&layout, options, device))
             .map_err(|e| Error::new(ErrorKind::Other, format!("{}", e)))?;
 
         thread::sleep(Duration::from_secs(delay));
 
-        for packet in hid_bytes.chunks(keyboard_layouts::HID_PACKET_LEN) {
-            fs::write(&hid_file, packet)?;
-            thread::sleep(Duration::from_millis(cooldown));
+        for step in steps {
+            match step {
+                Step::Reports(reports) => write_reports(&hid_file, &reports, cooldown)?,
+                Step::Delay(duration) => thread::sleep(duration),
+            }
         }
+
+        return Ok(());
+    }
+
+    if let Some(mut string) = string {
+        if newline {
+            string.push('\n');
+        }
+
+        let encoded = if packed {
+            keyboard_layouts::string_to_packed_hid_packets_with_options(&layout, &string, options)
+        } else {
+            keyboard_layouts::string_to_hid_packets_with_options(&layout, &string, options)
+        };
+        let (hid_bytes, alterations) =
+            encoded.map_err(|e| Error::new(ErrorKind::Other, format!("{}", e)))?;
+
+        for alteration in alterations {
+            match alteration.replacement {
+                Some(replacement) => eprintln!(
+                    "Replaced '{}' at {} with '{}'",
+                    alteration.original, alteration.index, replacement
+                ),
+                None => eprintln!("Skipped '{}' at {}", alteration.original, alteration.index),
+            }
+        }
+
+        thread::sleep(Duration::from_secs(delay));
+
+        let reports = device
+            .keyboard_reports(&hid_bytes)
+            .map_err(|e| Error::new(ErrorKind::Other, format!("{}", e)))?;
+
+        write_reports(&hid_file, &reports, cooldown)?;
+    }
+
+    Ok(())
+}
+
+fn write_reports(hid_file: &str, reports: &[Bytes], cooldown: u64) -> Result<()> {
+    for report in reports {
+        fs::write(hid_file, report)?;
+        thread::sleep(Duration::from_millis(cooldown));
     }
 
     Ok(())
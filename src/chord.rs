@@ -0,0 +1,192 @@
+use gen_layouts_sys::*;
+
+use crate::{
+    deadkey_for_keycode, key_for_keycode, keycode_for_unicode, modifier_for_keycode, Error, KeyMod,
+    Keycode, Release,
+};
+
+use std::fmt;
+use std::str::FromStr;
+
+const CHORD_SEPARATOR: char = '+';
+const KEY_PREFIX: &str = "KEY_";
+const MODIFIER_PREFIX: &str = "MODIFIERKEY_";
+
+/// The key pressed by a chord
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChordKey {
+    Key(Key),
+    /// Whichever key types the character with the layout, so Ctrl+'z' presses the key labelled Z
+    /// on a French keyboard. Any modifier the layout needs for the character is held as well.
+    Char(char),
+}
+
+/// Modifiers held while a key is pressed, e.g. Ctrl+Alt+Delete or Win+R. Without a key the
+/// modifiers are pressed and released on their own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chord {
+    pub modifiers: Vec<Modifier>,
+    pub key: Option<ChordKey>,
+}
+
+impl Chord {
+    pub fn new<K: Into<ChordKey>>(modifiers: &[Modifier], key: K) -> Chord {
+        Chord {
+            modifiers: modifiers.to_vec(),
+            key: Some(key.into()),
+        }
+    }
+
+    pub(crate) fn keys_and_modifiers(&self, layout: &Layout) -> Result<Vec<KeyMod>, Error> {
+        let modifier = self
+            .modifiers
+            .iter()
+            .fold(0u8, |bits, modifier| bits | modifier.bits());
+
+        let (key, key_modifier) = match self.key {
+            Some(ChordKey::Key(key)) => (key.usage(), 0),
+            Some(ChordKey::Char(c)) => match keycode_for_unicode(layout, c as u32) {
+                // A character behind a deadkey cannot be pressed in one go
+                Keycode::RegularKey(keycode) if deadkey_for_keycode(layout, keycode).is_none() => (
+                    key_for_keycode(layout, keycode),
+                    modifier_for_keycode(layout, keycode),
+                ),
+                _ => return Err(Error::InvalidCharacter(c)),
+            },
+            None => (0, 0),
+        };
+
+        let mut keys_and_modifiers = Vec::with_capacity(2);
+        // Hosts expect the modifiers to be down before the key
+        if modifier != 0 && key != 0 {
+            keys_and_modifiers.push(KeyMod {
+                key: 0,
+                modifier,
+                release: Release::None,
+                fallback: None,
+            });
+        }
+        keys_and_modifiers.push(KeyMod {
+            key,
+            modifier: modifier | key_modifier,
+            release: Release::All,
+            fallback: None,
+        });
+
+        Ok(keys_and_modifiers)
+    }
+}
+
+impl From<Key> for ChordKey {
+    fn from(key: Key) -> ChordKey {
+        ChordKey::Key(key)
+    }
+}
+
+impl From<char> for ChordKey {
+    fn from(c: char) -> ChordKey {
+        ChordKey::Char(c)
+    }
+}
+
+// Written the way chords are parsed, e.g. "Page_Up", with letters uppercase like the key labels.
+// Keys named by a single character keep their prefix, e.g. "KEY_A", so they are not taken as the
+// character typed with the layout.
+impl fmt::Display for ChordKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChordKey::Key(key) => {
+                let name = key.name().trim_start_matches(KEY_PREFIX);
+                if name.chars().count() == 1 {
+                    write!(f, "{}", key.name())
+                } else {
+                    write!(f, "{}", title_case(name))
+                }
+            }
+            ChordKey::Char(c) => write!(f, "{}", c.to_ascii_uppercase()),
+        }
+    }
+}
+
+// Parses back to the same chord, e.g. "Ctrl+Alt+Delete" or "Gui+R"
+impl fmt::Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = self
+            .modifiers
+            .iter()
+            .map(|modifier| title_case(modifier.name().trim_start_matches(MODIFIER_PREFIX)))
+            .collect::<Vec<String>>();
+        if let Some(key) = self.key {
+            parts.push(key.to_string());
+        }
+        write!(f, "{}", parts.join("+"))
+    }
+}
+
+// "Ctrl+Alt+Delete", "Win+R", "Cmd+Space", "Ctrl++" or just "Win". A single character is typed
+// with the layout and letters are taken as lowercase, so "Ctrl+Shift+Z" is needed for 'Z'.
+impl FromStr for Chord {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Chord, Error> {
+        let invalid_chord = || Error::InvalidChord(s.to_string());
+
+        // "Ctrl++" and "Ctrl + +" both press Ctrl and the key typing '+'
+        let plus_modifiers = s
+            .trim_end()
+            .strip_suffix(CHORD_SEPARATOR)
+            .and_then(|modifiers| modifiers.trim_end().strip_suffix(CHORD_SEPARATOR));
+        let (modifiers, key) = if let Some(modifiers) = plus_modifiers {
+            (modifiers, "+")
+        } else {
+            match s.rfind(CHORD_SEPARATOR) {
+                Some(idx) => (&s[..idx], &s[idx + 1..]),
+                None => ("", s),
+            }
+        };
+        // Spaces around the key are ignored unless the key is a space, so "Ctrl + z" is "Ctrl+z"
+        let key = match key.trim() {
+            "" => key,
+            trimmed => trimmed,
+        };
+
+        let mut modifiers = if modifiers.is_empty() {
+            Vec::new()
+        } else {
+            modifiers
+                .split(CHORD_SEPARATOR)
+                .map(|modifier| modifier.trim().parse::<Modifier>())
+                .collect::<Result<Vec<Modifier>, InvalidModifier>>()
+                .map_err(|_| invalid_chord())?
+        };
+
+        let mut chars = key.chars();
+        let key = match (chars.next(), chars.next()) {
+            (Some(c), None) => Some(ChordKey::Char(c.to_ascii_lowercase())),
+            _ => match (key.parse::<Key>(), key.parse::<Modifier>()) {
+                (Ok(key), _) => Some(ChordKey::Key(key)),
+                (_, Ok(modifier)) => {
+                    modifiers.push(modifier);
+                    None
+                }
+                _ => return Err(invalid_chord()),
+            },
+        };
+
+        Ok(Chord { modifiers, key })
+    }
+}
+
+// "PAGE_UP" -> "Page_Up"
+fn title_case(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_string() + &chars.as_str().to_lowercase(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("_")
+}
@@ -0,0 +1,52 @@
+use gen_layouts_sys::*;
+
+use crate::keymap::{normalise_shift, KeyMap};
+use crate::{Error, HID_PACKET_LEN};
+
+// Byte offsets within a HID packet
+const MODIFIER_OFFSET: usize = 0;
+const KEYS_OFFSET: usize = 2;
+
+// Only keys that were not already held in the previous packet type anything, in the order of
+// their slots. A deadkey is composed with the next key pressed after it, if they compose.
+pub(crate) fn decode(layout: &Layout, packets: &[u8]) -> Result<String, Error> {
+    if packets.len() % HID_PACKET_LEN != 0 {
+        return Err(Error::InvalidPacketLength(packets.len()));
+    }
+
+    let key_map = KeyMap::new(layout);
+    let mut string = String::new();
+    let mut held: &[u8] = &[];
+    let mut deadkey = None;
+
+    for (idx, packet) in packets.chunks(HID_PACKET_LEN).enumerate() {
+        let modifier = normalise_shift(packet[MODIFIER_OFFSET]);
+        let keys = &packet[KEYS_OFFSET..];
+
+        for &key in keys.iter().filter(|key| **key != 0 && !held.contains(key)) {
+            let press = (key, modifier);
+            if let Some(deadkey) = deadkey.take() {
+                if let Some(c) = key_map.compose(deadkey, press) {
+                    string.push(c);
+                    continue;
+                }
+                // A host types the accent on its own and then the key, e.g. "^x"
+                string.extend(key_map.accent(deadkey).map(Accent::spacing_char));
+            }
+            if key_map.is_deadkey(press) {
+                deadkey = Some(press);
+                continue;
+            }
+
+            string.push(key_map.char_for(press).ok_or(Error::UnknownKeyPress {
+                packet: idx,
+                key,
+                modifier: packet[MODIFIER_OFFSET],
+            })?);
+        }
+
+        held = keys;
+    }
+
+    Ok(string)
+}
@@ -0,0 +1,165 @@
+use gen_layouts_sys::{Key, Modifier};
+
+use crate::script::{
+    column_of, is_repeatable, parse_number, parse_repeat_count, Command, ScriptLine,
+};
+use crate::{Chord, ChordKey, Error, Script};
+
+use std::time::Duration;
+
+// https://docs.hak5.org/hak5-usb-rubber-ducky/ducky-script-basics/keystroke-injection
+impl Script {
+    /// Translate a DuckyScript payload. `REM`, `STRING`, `STRINGLN`, `DELAY`, `DEFAULT_DELAY`,
+    /// `REPEAT`, `HOLD`, `RELEASE` and key lines such as `GUI r` or `CTRL-ALT DELETE` are
+    /// supported. Anything else, e.g. variables or `ATTACKMODE`, fails with the line and column.
+    pub fn parse_ducky(script: &str) -> Result<Script, Error> {
+        let mut lines = Vec::new();
+
+        for (idx, line) in script.lines().enumerate() {
+            let line_number = idx + 1;
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            let keyword_start = line.len() - trimmed.len();
+            let keyword_end = trimmed
+                .find(char::is_whitespace)
+                .map_or(line.len(), |idx| keyword_start + idx);
+            let keyword = &line[keyword_start..keyword_end];
+            let argument_start = line[keyword_end..]
+                .chars()
+                .next()
+                .map_or(keyword_end, |c| keyword_end + c.len_utf8());
+            let argument = &line[argument_start..];
+            let column = column_of(line, argument_start);
+            let error = |column: usize, message: String| Error::InvalidScript {
+                line: line_number,
+                column,
+                message,
+            };
+
+            // Keywords are case sensitive in DuckyScript
+            let command = match keyword {
+                "REM" => continue,
+                "STRING" => Command::Type(argument.to_string()),
+                "STRINGLN" => Command::Type(format!("{}\n", argument)),
+                "DELAY" | "DEFAULT_DELAY" | "DEFAULTDELAY" => {
+                    let millis = parse_number(argument).map_err(|e| error(column, e))?;
+                    match keyword {
+                        "DELAY" => Command::Delay(Duration::from_millis(millis)),
+                        _ => Command::DefaultDelay(Duration::from_millis(millis)),
+                    }
+                }
+                "REPEAT" | "REPLAY" => {
+                    let count = parse_repeat_count(argument).map_err(|e| error(column, e))?;
+                    if !lines.iter().any(is_repeatable) {
+                        return Err(error(
+                            column,
+                            format!("{} needs a command before it", keyword),
+                        ));
+                    }
+                    Command::Repeat(count)
+                }
+                "HOLD" | "RELEASE" => {
+                    let chord = parse_keys(line, argument_start)
+                        .map_err(|(column, message)| error(column, message))?;
+                    match (keyword, chord) {
+                        ("HOLD", Some(chord)) => Command::Hold(vec![chord]),
+                        ("HOLD", None) => {
+                            return Err(error(column, "HOLD needs a key or modifier".to_string()))
+                        }
+                        (_, chord) => Command::Release(chord.into_iter().collect()),
+                    }
+                }
+                _ => {
+                    let keyword_column = column_of(line, keyword_start);
+                    match parse_keys(line, keyword_start) {
+                        Ok(Some(chord)) => Command::Press(vec![chord]),
+                        // A key line with a bad key after the first
+                        Err((column, message)) if column != keyword_column => {
+                            return Err(error(column, message))
+                        }
+                        _ => {
+                            return Err(error(
+                                keyword_column,
+                                format!("Unsupported DuckyScript command: {}", keyword),
+                            ))
+                        }
+                    }
+                }
+            };
+
+            lines.push(ScriptLine {
+                line: line_number,
+                column,
+                command,
+            });
+        }
+
+        Ok(Script { lines })
+    }
+}
+
+// A key line such as "GUI r", "CTRL-ALT DELETE" or "SHIFT". Fails with the column of the first
+// word that is not a key or modifier.
+fn parse_keys(line: &str, start: usize) -> Result<Option<Chord>, (usize, String)> {
+    let mut modifiers = Vec::new();
+    let mut key = None;
+
+    for word in line[start..].split(|c: char| c.is_whitespace() || c == '-') {
+        if word.is_empty() {
+            continue;
+        }
+        // Separators may be more than one byte long, e.g. a no-break space
+        let column = column_of(line, word.as_ptr() as usize - line.as_ptr() as usize);
+
+        let mut chars = word.chars();
+        let parsed = match (chars.next(), chars.next()) {
+            (Some(c), None) => Some(ChordKey::Char(c.to_ascii_lowercase())),
+            _ => match ducky_key(word) {
+                Some(key) => Some(ChordKey::Key(key)),
+                None => match word.parse::<Modifier>() {
+                    Ok(modifier) => {
+                        modifiers.push(modifier);
+                        None
+                    }
+                    Err(_) => {
+                        return Err((column, format!("Unsupported DuckyScript key: {}", word)))
+                    }
+                },
+            },
+        };
+
+        if let Some(parsed) = parsed {
+            if key.is_some() {
+                return Err((
+                    column,
+                    format!("Only one key can be pressed at once: {}", word),
+                ));
+            }
+            key = Some(parsed);
+        }
+    }
+
+    if modifiers.is_empty() && key.is_none() {
+        Ok(None)
+    } else {
+        Ok(Some(Chord { modifiers, key }))
+    }
+}
+
+// DuckyScript's own names for keys, any other name is parsed like in a chord
+fn ducky_key(name: &str) -> Option<Key> {
+    let key = match name {
+        "UPARROW" => Key::Up,
+        "DOWNARROW" => Key::Down,
+        "LEFTARROW" => Key::Left,
+        "RIGHTARROW" => Key::Right,
+        "BREAK" => Key::Pause,
+        "APP" => Key::Menu,
+        _ => return name.parse().ok(),
+    };
+
+    Some(key)
+}
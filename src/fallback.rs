@@ -0,0 +1,176 @@
+use gen_layouts_sys::*;
+
+use crate::{
+    ascii_keycode, key_for_keycode, modifier_for_keycode, push_regular_key, Error, KeyMod, Release,
+};
+
+use std::fmt;
+use std::str::FromStr;
+
+// The Unicode Hex Input source types as a US keyboard whatever layout the host normally uses
+const UNICODE_HEX_INPUT_LAYOUT: &str = "LAYOUT_US_ENGLISH";
+// Windows treats Alt codes below 256 without a leading zero as OEM code page characters
+const ALT_NUMPAD_ANSI_LIMIT: u32 = 0x100;
+
+/// How to type a character that the keyboard layout has no mapping for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Fallback {
+    /// Fail with `Error::InvalidCharacter`
+    #[default]
+    None,
+    /// Hold Left-Alt and type the decimal code point on the keypad (Windows). NumLock is pressed
+    /// before and after the code when `toggle_numlock` is set.
+    AltNumpad { toggle_numlock: bool },
+    /// Press Ctrl+Shift+U, type the hexadecimal code point and confirm with Space (Linux IBus and
+    /// GTK). The hex digits are typed using the current layout.
+    CtrlShiftU,
+    /// Hold Option and type each UTF-16 code unit as four hex digits (macOS "Unicode Hex Input"
+    /// source). Code points above U+FFFF are typed as a surrogate pair.
+    UnicodeHexInput,
+}
+
+impl fmt::Display for Fallback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Fallback::None => write!(f, "none"),
+            Fallback::AltNumpad {
+                toggle_numlock: false,
+            } => write!(f, "alt-numpad"),
+            Fallback::AltNumpad {
+                toggle_numlock: true,
+            } => write!(f, "alt-numpad-numlock"),
+            Fallback::CtrlShiftU => write!(f, "ctrl-shift-u"),
+            Fallback::UnicodeHexInput => write!(f, "unicode-hex-input"),
+        }
+    }
+}
+
+impl FromStr for Fallback {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Fallback, Error> {
+        match s {
+            "none" => Ok(Fallback::None),
+            "alt-numpad" => Ok(Fallback::AltNumpad {
+                toggle_numlock: false,
+            }),
+            "alt-numpad-numlock" => Ok(Fallback::AltNumpad {
+                toggle_numlock: true,
+            }),
+            "ctrl-shift-u" => Ok(Fallback::CtrlShiftU),
+            "unicode-hex-input" => Ok(Fallback::UnicodeHexInput),
+            _ => Err(Error::InvalidFallback(s.to_string())),
+        }
+    }
+}
+
+impl Fallback {
+    pub(crate) fn keys_and_modifiers(self, layout: &Layout, c: char) -> Result<Vec<KeyMod>, Error> {
+        let mut keys_and_modifiers = match self {
+            Fallback::None => return Err(Error::InvalidCharacter(c)),
+            Fallback::AltNumpad { toggle_numlock } => alt_numpad(c, toggle_numlock),
+            Fallback::CtrlShiftU => ctrl_shift_u(layout, c),
+            Fallback::UnicodeHexInput => unicode_hex_input(c),
+        };
+
+        for key_mod in keys_and_modifiers.iter_mut() {
+            key_mod.fallback = Some(self);
+        }
+
+        Ok(keys_and_modifiers)
+    }
+}
+
+// https://en.wikipedia.org/wiki/Alt_code
+fn alt_numpad(c: char, toggle_numlock: bool) -> Vec<KeyMod> {
+    let unicode = c as u32;
+    let mut digits = unicode.to_string();
+    if unicode < ALT_NUMPAD_ANSI_LIMIT {
+        digits.insert(0, '0');
+    }
+
+    let mut keys_and_modifiers = Vec::with_capacity(digits.len() + 3);
+
+    if toggle_numlock {
+        keys_and_modifiers.push(numlock());
+    }
+
+    for digit in digits.chars().filter_map(|d| d.to_digit(10)) {
+        keys_and_modifiers.push(KeyMod {
+            key: NUMPAD_KEYS[digit as usize] as u8,
+            modifier: LEFT_ALT_MODIFIER as u8,
+            release: Release::Keys,
+            fallback: None,
+        });
+    }
+    // Releasing Left-Alt is what enters the character
+    keys_and_modifiers.push(KeyMod {
+        key: 0,
+        modifier: 0,
+        release: Release::None,
+        fallback: None,
+    });
+
+    if toggle_numlock {
+        keys_and_modifiers.push(numlock());
+    }
+
+    keys_and_modifiers
+}
+
+// https://help.gnome.org/users/gnome-help/stable/tips-specialchars.html#ctrlshiftu
+fn ctrl_shift_u(layout: &Layout, c: char) -> Vec<KeyMod> {
+    let mut keys_and_modifiers = vec![KeyMod {
+        key: key_for_keycode(layout, ascii_keycode(layout, 'u')),
+        modifier: (RIGHT_CTRL_MODIFIER | SHIFT_MODIFIER) as u8,
+        release: Release::All,
+        fallback: None,
+    }];
+
+    for digit in format!("{:x}", c as u32).chars() {
+        push_regular_key(
+            layout,
+            ascii_keycode(layout, digit),
+            &mut keys_and_modifiers,
+        );
+    }
+    push_regular_key(layout, ascii_keycode(layout, ' '), &mut keys_and_modifiers);
+
+    keys_and_modifiers
+}
+
+// https://support.apple.com/guide/mac-help/use-the-unicode-hex-input-source-mchlp1406/mac
+fn unicode_hex_input(c: char) -> Vec<KeyMod> {
+    let layout = &LAYOUT_MAP[UNICODE_HEX_INPUT_LAYOUT];
+    let mut keys_and_modifiers = Vec::new();
+
+    for code_unit in c.encode_utf16(&mut [0u16; 2]).iter() {
+        for digit in format!("{:04x}", code_unit).chars() {
+            let keycode = ascii_keycode(layout, digit);
+            keys_and_modifiers.push(KeyMod {
+                key: key_for_keycode(layout, keycode),
+                modifier: modifier_for_keycode(layout, keycode) | LEFT_ALT_MODIFIER as u8,
+                release: Release::Keys,
+                fallback: None,
+            });
+        }
+    }
+    // Option stays held across both halves of a surrogate pair
+    keys_and_modifiers.push(KeyMod {
+        key: 0,
+        modifier: 0,
+        release: Release::None,
+        fallback: None,
+    });
+
+    keys_and_modifiers
+}
+
+fn numlock() -> KeyMod {
+    KeyMod {
+        key: NUMLOCK as u8,
+        modifier: 0,
+        release: Release::All,
+        fallback: None,
+    }
+}
@@ -0,0 +1,100 @@
+use bytes::{BufMut, Bytes, BytesMut};
+
+use crate::{Error, HID_PACKET_LEN};
+
+use std::fmt;
+use std::str::FromStr;
+
+const NKRO_PREFIX: &str = "nkro:";
+// Byte offsets within a boot report
+const MODIFIER_OFFSET: usize = 0;
+const KEYS_OFFSET: usize = 2;
+
+/// How the pressed keys are laid out in a keyboard report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyRollover {
+    /// The 8 byte boot protocol report: modifiers, a reserved byte and 6 key slots
+    #[default]
+    Boot,
+    /// The modifiers followed by a bitmap of the given number of bytes, with bit `usage % 8` of
+    /// byte `usage / 8` set for each pressed key
+    Nkro { bytes: usize },
+}
+
+/// The keyboard report layout a device expects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ReportFormat {
+    pub rollover: KeyRollover,
+    /// Written before every report when the device's descriptor declares a report ID
+    pub report_id: Option<u8>,
+}
+
+impl ReportFormat {
+    /// The number of bytes in each report, including any report ID
+    pub fn report_len(self) -> usize {
+        let report_id_len = if self.report_id.is_some() { 1 } else { 0 };
+        let keys_len = match self.rollover {
+            KeyRollover::Boot => HID_PACKET_LEN,
+            KeyRollover::Nkro { bytes } => 1 + bytes,
+        };
+        report_id_len + keys_len
+    }
+
+    /// Render 8 byte boot packets, as created by `keys_and_modifiers_to_hid_packets`, as reports
+    /// in this format. Fails if the packets are not a multiple of 8 bytes long or if an NKRO bitmap
+    /// is too small for a key that is pressed.
+    pub fn render(self, packets: &[u8]) -> Result<Bytes, Error> {
+        if packets.len() % HID_PACKET_LEN != 0 {
+            return Err(Error::InvalidPacketLength(packets.len()));
+        }
+        let n_packets = packets.len() / HID_PACKET_LEN;
+        let mut reports = BytesMut::with_capacity(n_packets * self.report_len());
+
+        for packet in packets.chunks(HID_PACKET_LEN) {
+            if let Some(report_id) = self.report_id {
+                reports.put_u8(report_id);
+            }
+            match self.rollover {
+                KeyRollover::Boot => reports.put_slice(packet),
+                KeyRollover::Nkro { bytes } => {
+                    let mut bitmap = vec![0u8; bytes];
+                    for &key in packet[KEYS_OFFSET..].iter().filter(|key| **key != 0) {
+                        let byte = bitmap.get_mut(key as usize / 8).ok_or_else(|| {
+                            Error::UnsupportedByDevice(format!("Key usage {:#04X}", key))
+                        })?;
+                        *byte |= 1 << (key % 8);
+                    }
+                    reports.put_u8(packet[MODIFIER_OFFSET]);
+                    reports.put_slice(&bitmap);
+                }
+            }
+        }
+
+        Ok(reports.freeze())
+    }
+}
+
+impl fmt::Display for KeyRollover {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KeyRollover::Boot => write!(f, "boot"),
+            KeyRollover::Nkro { bytes } => write!(f, "{}{}", NKRO_PREFIX, bytes),
+        }
+    }
+}
+
+impl FromStr for KeyRollover {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<KeyRollover, Error> {
+        match s {
+            "boot" => Ok(KeyRollover::Boot),
+            _ => s
+                .strip_prefix(NKRO_PREFIX)
+                .and_then(|bytes| bytes.parse::<usize>().ok())
+                .filter(|bytes| *bytes > 0)
+                .map(|bytes| KeyRollover::Nkro { bytes })
+                .ok_or_else(|| Error::InvalidReportFormat(s.to_string())),
+        }
+    }
+}
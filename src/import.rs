@@ -0,0 +1,189 @@
+use gen_layouts_sys::{Accent, DeadKey, Layout};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::validate::validate;
+use crate::{UNICODE_FIRST_ASCII, UNICODE_FIRST_LATIN1, UNICODE_LAST_ASCII, UNICODE_LAST_LATIN1};
+
+// Imported layouts use the same keycode bits as the German layout in the Teensy header, with a
+// fourth deadkey bit so up to 15 deadkeys fit
+const SHIFT_MASK: u16 = 0x40;
+const ALT_MASK: u16 = 0x80;
+const DEAD_KEYS_MASK: u16 = 0xF00;
+const DEAD_KEY_SHIFT: u16 = 8;
+const KEYCODE_MASK: u16 = 0xFFF;
+// The Non-US \ key does not fit the keycode, so it stands in for the last usage that does
+const MAX_USAGE: u8 = 0x3F;
+const NON_US_USAGE: u8 = 0x64;
+const NON_US_KEY: u16 = 0x3F;
+const SPACE_USAGE: u8 = 0x2C;
+const BACKSPACE_USAGE: u8 = 0x2A;
+
+/// The modifiers held to type a character, in the order their characters are preferred
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Level {
+    Base,
+    Shift,
+    AltGr,
+    ShiftAltGr,
+}
+
+impl Level {
+    // Layout files number the levels in this order too
+    pub(crate) fn from_index(idx: usize) -> Option<Level> {
+        match idx {
+            0 => Some(Level::Base),
+            1 => Some(Level::Shift),
+            2 => Some(Level::AltGr),
+            3 => Some(Level::ShiftAltGr),
+            _ => None,
+        }
+    }
+
+    fn bits(self) -> u16 {
+        match self {
+            Level::Base => 0,
+            Level::Shift => SHIFT_MASK,
+            Level::AltGr => ALT_MASK,
+            Level::ShiftAltGr => SHIFT_MASK | ALT_MASK,
+        }
+    }
+}
+
+// Builds a `Layout` from what each key types, for the importers of other layout formats
+#[derive(Default)]
+pub(crate) struct LayoutBuilder {
+    chars: Vec<(char, u8, Level)>,
+    deadkeys: Vec<(Accent, u8, Level)>,
+    // (accent, base, composed) for layouts that list what each deadkey types
+    composed: Vec<(Accent, char, char)>,
+}
+
+impl LayoutBuilder {
+    pub(crate) fn add_char(&mut self, c: char, usage: u8, level: Level) {
+        self.chars.push((c, usage, level));
+    }
+
+    pub(crate) fn add_deadkey(&mut self, accent: Accent, usage: u8, level: Level) {
+        self.deadkeys.push((accent, usage, level));
+    }
+
+    // A deadkey followed by the base character types the composed character. Deadkeys without any
+    // are composed like Unicode composes the base character and the accent.
+    pub(crate) fn add_composed(&mut self, accent: Accent, base: char, composed: char) {
+        self.composed.push((accent, base, composed));
+    }
+
+    // A character or deadkey typed several ways uses the one with the fewest modifiers, then the
+    // first added. Fails if the layout cannot be typed on, e.g. one without the Latin letters.
+    pub(crate) fn build(mut self) -> Result<Layout, String> {
+        let mut non_us = None;
+        let mut keycode_for = |usage: u8, level: Level| match usage {
+            NON_US_USAGE => {
+                non_us = Some(NON_US_KEY);
+                Some(NON_US_KEY | level.bits())
+            }
+            0 | MAX_USAGE => None,
+            u if u < MAX_USAGE => Some(u16::from(u) | level.bits()),
+            _ => None,
+        };
+
+        self.chars.sort_by_key(|(_, _, level)| *level);
+        let mut keycodes: Vec<(char, u16)> = Vec::new();
+        for (c, usage, level) in self.chars.iter() {
+            if let Some(keycode) = keycode_for(*usage, *level) {
+                if !keycodes.iter().any(|(existing, _)| existing == c) {
+                    keycodes.push((*c, keycode));
+                }
+            }
+        }
+        // Every keyboard has space and backspace in the same place
+        for (c, usage) in [(' ', SPACE_USAGE), ('\u{7F}', BACKSPACE_USAGE)].iter() {
+            if !keycodes.iter().any(|(existing, _)| existing == c) {
+                keycodes.push((*c, u16::from(*usage)));
+            }
+        }
+
+        self.deadkeys.sort_by_key(|(_, _, level)| *level);
+        let mut deadkeys: Vec<DeadKey> = Vec::new();
+        for (accent, usage, level) in self.deadkeys.iter() {
+            let bits = (deadkeys.len() as u16 + 1) << DEAD_KEY_SHIFT;
+            if deadkeys.iter().any(|deadkey| deadkey.accent == *accent) || bits > DEAD_KEYS_MASK {
+                continue;
+            }
+            if let Some(keycode) = keycode_for(*usage, *level) {
+                deadkeys.push(DeadKey::new(*accent, bits, keycode));
+            }
+        }
+
+        let direct = keycodes.clone();
+        let mut compose = |deadkey: &DeadKey, base: char, composed: char| {
+            let base_keycode = direct.iter().find(|(c, _)| *c == base).map(|(_, k)| *k);
+            if let Some(base_keycode) = base_keycode {
+                if !keycodes.iter().any(|(existing, _)| *existing == composed) {
+                    keycodes.push((composed, base_keycode | deadkey.bits));
+                }
+            }
+        };
+        for deadkey in deadkeys.iter() {
+            let listed: Vec<(char, char)> = self
+                .composed
+                .iter()
+                .filter(|(accent, _, _)| *accent == deadkey.accent)
+                .map(|(_, base, composed)| (*base, *composed))
+                .collect();
+            if !listed.is_empty() {
+                for (base, composed) in listed {
+                    compose(deadkey, base, composed);
+                }
+                continue;
+            }
+
+            for (base, _) in direct.iter() {
+                let mut nfc = std::iter::once(*base)
+                    .chain(std::iter::once(deadkey.accent.combining_char()))
+                    .nfc();
+                if let (Some(composed), None) = (nfc.next(), nfc.next()) {
+                    if composed != *base {
+                        compose(deadkey, *base, composed);
+                    }
+                }
+            }
+            compose(deadkey, ' ', deadkey.accent.spacing_char());
+        }
+
+        let keycode_of = |unicode: u32| {
+            std::char::from_u32(unicode)
+                .and_then(|c| keycodes.iter().find(|(existing, _)| *existing == c))
+                .map(|(_, keycode)| *keycode)
+                .unwrap_or(0)
+        };
+        let ascii = (UNICODE_FIRST_ASCII..=UNICODE_LAST_ASCII)
+            .map(keycode_of)
+            .collect();
+        let latin1 = (UNICODE_FIRST_LATIN1..=UNICODE_LAST_LATIN1)
+            .map(keycode_of)
+            .collect();
+        let extra = keycodes
+            .iter()
+            .filter(|(c, _)| *c as u32 > UNICODE_LAST_LATIN1 && *c as u32 <= 0xFFFF)
+            .map(|(c, keycode)| (*c as u16, *keycode))
+            .collect();
+
+        validate(Layout::new(
+            SHIFT_MASK,
+            Some(ALT_MASK),
+            None,
+            non_us,
+            if deadkeys.is_empty() {
+                None
+            } else {
+                Some(DEAD_KEYS_MASK)
+            },
+            KEYCODE_MASK,
+            ascii,
+            latin1,
+            extra,
+            deadkeys,
+        ))
+    }
+}
@@ -0,0 +1,53 @@
+use gen_layouts_sys::{Key, MediaKey, SystemKey};
+
+use crate::Chord;
+
+/// Part of a stream of key presses: text typed using the layout or a named key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    Text(String),
+    /// Pressed and released on its own. Named keys are physical keys so they are not affected
+    /// by the layout, e.g. `Key::A` types 'q' on a French keyboard.
+    Key(Key),
+    Chord(Chord),
+    /// Only a device with a consumer control report can send media keys, see `Device`
+    Media(MediaKey),
+    /// Only a device with a system control report can send system keys, see `Device`
+    System(SystemKey),
+}
+
+impl From<&str> for Input {
+    fn from(text: &str) -> Input {
+        Input::Text(text.to_string())
+    }
+}
+
+impl From<String> for Input {
+    fn from(text: String) -> Input {
+        Input::Text(text)
+    }
+}
+
+impl From<Key> for Input {
+    fn from(key: Key) -> Input {
+        Input::Key(key)
+    }
+}
+
+impl From<Chord> for Input {
+    fn from(chord: Chord) -> Input {
+        Input::Chord(chord)
+    }
+}
+
+impl From<MediaKey> for Input {
+    fn from(media_key: MediaKey) -> Input {
+        Input::Media(media_key)
+    }
+}
+
+impl From<SystemKey> for Input {
+    fn from(system_key: SystemKey) -> Input {
+        Input::System(system_key)
+    }
+}
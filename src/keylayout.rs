@@ -0,0 +1,272 @@
+use gen_layouts_sys::{Accent, Layout};
+use roxmltree::{Document, Node, ParsingOptions};
+
+use crate::import::{LayoutBuilder, Level};
+use crate::Error;
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+// The state a key press starts in when no deadkey was pressed before it
+const NO_STATE: &str = "none";
+// Key maps based on other key maps deeper than this are taken to be a cycle
+const MAX_BASE_DEPTH: usize = 16;
+const LEVELS: [Level; 4] = [Level::Base, Level::Shift, Level::AltGr, Level::ShiftAltGr];
+
+/// Load a layout from a macOS `.keylayout` file, e.g. one made with Ukelele. Option is typed as
+/// the Alt modifier, so the key maps for no modifiers, Shift, Option and Shift+Option are typed
+/// with none, Shift, AltGr and Shift+AltGr. Actions that enter a state are deadkeys, with the
+/// accent of the state's terminator, and type exactly the characters their states list.
+pub fn load_keylayout<P: AsRef<Path>>(path: P) -> Result<Layout, Error> {
+    let path = path.as_ref();
+    let invalid_file =
+        |message: String| Error::InvalidLayoutFile(format!("{}: {}", path.display(), message));
+
+    let contents = fs::read_to_string(path).map_err(|e| invalid_file(e.to_string()))?;
+    let contents = without_control_references(&contents);
+    let options = ParsingOptions {
+        allow_dtd: true,
+        ..ParsingOptions::default()
+    };
+    let document = Document::parse_with_options(&contents, options)
+        .map_err(|e| invalid_file(e.to_string()))?;
+    parse(&document).map_err(invalid_file)
+}
+
+// Key layouts are XML 1.1 so they can output control characters, e.g. &#x0008; for Delete. XML 1.0
+// parsers refuse those, and they only ever type on keys without a character, so they are dropped.
+fn without_control_references(contents: &str) -> String {
+    let mut output = String::with_capacity(contents.len());
+    let mut rest = contents;
+    while let Some(start) = rest.find("&#") {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = match rest.find(';') {
+            Some(end) => end,
+            None => break,
+        };
+        let reference = &rest[2..end];
+        let unicode = match reference.strip_prefix('x') {
+            Some(hex) => u32::from_str_radix(hex, 16).ok(),
+            None => reference.parse::<u32>().ok(),
+        };
+        match unicode {
+            Some(u) if u < 0x20 && u != 0x09 && u != 0x0A && u != 0x0D => {}
+            _ => output.push_str(&rest[..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    output.push_str(rest);
+    output
+}
+
+fn parse(document: &Document) -> Result<Layout, String> {
+    let keyboard = document.root_element();
+    let layouts = elements(keyboard, "layouts")
+        .flat_map(|layouts| elements(layouts, "layout"))
+        .collect::<Vec<Node>>();
+    // The layout for the keyboard types starting at 0, those with the usual keys
+    let layout = layouts
+        .iter()
+        .find(|layout| layout.attribute("first") == Some("0"))
+        .or_else(|| layouts.first())
+        .ok_or_else(|| "no layouts".to_string())?;
+    let map_set = attribute(*layout, "mapSet")?;
+    let modifiers = attribute(*layout, "modifiers")?;
+
+    let modifier_map = elements(keyboard, "modifierMap")
+        .find(|map| map.attribute("id") == Some(modifiers))
+        .ok_or_else(|| format!("no modifierMap {}", modifiers))?;
+    let actions = elements(keyboard, "actions")
+        .flat_map(|actions| elements(actions, "action"))
+        .filter_map(|action| action.attribute("id").map(|id| (id, action)))
+        .collect::<HashMap<&str, Node>>();
+    // A deadkey state's accent is its terminator, typed when the next key has no output for the
+    // state, e.g. '^'
+    let accents = elements(keyboard, "terminators")
+        .flat_map(|terminators| elements(terminators, "when"))
+        .filter_map(|when| {
+            let output = single_char(when.attribute("output")?)?;
+            Some((when.attribute("state")?, Accent::from_char(output)?))
+        })
+        .collect::<HashMap<&str, Accent>>();
+
+    let mut builder = LayoutBuilder::default();
+    for level in LEVELS.iter() {
+        let index = key_map_index(modifier_map, *level)?;
+        for (code, key) in keys(keyboard, map_set, &index, 0)? {
+            let usage = match usage_for_key_code(code) {
+                Some(usage) => usage,
+                None => continue,
+            };
+            let action = match (key.attribute("output"), key.attribute("action")) {
+                (Some(output), _) => {
+                    if let Some(c) = single_char(output) {
+                        builder.add_char(c, usage, *level);
+                    }
+                    continue;
+                }
+                (None, Some(id)) => actions.get(id).copied(),
+                // Actions may also be written inside the key
+                (None, None) => elements(key, "action").next(),
+            };
+            let whens = match action {
+                Some(action) => elements(action, "when").collect::<Vec<Node>>(),
+                None => continue,
+            };
+
+            let initial = whens
+                .iter()
+                .find(|when| when.attribute("state") == Some(NO_STATE));
+            let base = initial
+                .and_then(|when| when.attribute("output"))
+                .and_then(single_char);
+            if let Some(c) = base {
+                builder.add_char(c, usage, *level);
+            }
+            let next = initial.and_then(|when| when.attribute("next"));
+            if let Some(accent) = next.and_then(|state| accents.get(state)) {
+                builder.add_deadkey(*accent, usage, *level);
+            }
+
+            for when in whens.iter() {
+                let state = when.attribute("state").unwrap_or(NO_STATE);
+                let composed = when.attribute("output").and_then(single_char);
+                if let (Some(accent), Some(base), Some(composed)) =
+                    (accents.get(state), base, composed)
+                {
+                    builder.add_composed(*accent, base, composed);
+                }
+            }
+        }
+    }
+
+    builder.build()
+}
+
+// The index of the key map used while the level's modifiers are held, with Caps Lock off
+fn key_map_index(modifier_map: Node, level: Level) -> Result<String, String> {
+    let (shift, option) = match level {
+        Level::Base => (false, false),
+        Level::Shift => (true, false),
+        Level::AltGr => (false, true),
+        Level::ShiftAltGr => (true, true),
+    };
+
+    elements(modifier_map, "keyMapSelect")
+        .find(|select| {
+            elements(*select, "modifier").any(|modifier| {
+                modifier
+                    .attribute("keys")
+                    .is_some_and(|keys| modifiers_match(keys, shift, option))
+            })
+        })
+        .and_then(|select| select.attribute("mapIndex"))
+        .or_else(|| modifier_map.attribute("defaultIndex"))
+        .map(str::to_string)
+        .ok_or_else(|| "no keyMapSelect for the modifiers and no defaultIndex".to_string())
+}
+
+// Modifier keys are listed like "anyShift caps? command", those ending in ? are optional.
+// Either Shift or Option key matches, only Shift and Option are ever held.
+fn modifiers_match(keys: &str, shift: bool, option: bool) -> bool {
+    let mut shift_listed = false;
+    let mut option_listed = false;
+    for key in keys.split_whitespace() {
+        let (key, optional) = match key.strip_suffix('?') {
+            Some(key) => (key, true),
+            None => (key, false),
+        };
+        let held = match key {
+            "shift" | "rightShift" | "anyShift" => {
+                shift_listed = true;
+                shift
+            }
+            "option" | "rightOption" | "anyOption" => {
+                option_listed = true;
+                option
+            }
+            _ => false,
+        };
+        if !held && !optional {
+            return false;
+        }
+    }
+
+    (shift_listed || !shift) && (option_listed || !option)
+}
+
+// The keys of a key map by their virtual key code, including those of the key map it is based on
+fn keys<'a, 'input>(
+    keyboard: Node<'a, 'input>,
+    map_set: &str,
+    index: &str,
+    depth: usize,
+) -> Result<Vec<(u16, Node<'a, 'input>)>, String> {
+    if depth > MAX_BASE_DEPTH {
+        return Err("key maps are based on each other too deeply".to_string());
+    }
+    let key_map = elements(keyboard, "keyMapSet")
+        .filter(|set| set.attribute("id") == Some(map_set))
+        .flat_map(|set| elements(set, "keyMap"))
+        .find(|map| map.attribute("index") == Some(index))
+        .ok_or_else(|| format!("no keyMap {} in keyMapSet {}", index, map_set))?;
+
+    let mut keys = match (
+        key_map.attribute("baseMapSet"),
+        key_map.attribute("baseIndex"),
+    ) {
+        (Some(base_map_set), Some(base_index)) => {
+            self::keys(keyboard, base_map_set, base_index, depth + 1)?
+        }
+        _ => Vec::new(),
+    };
+    for key in elements(key_map, "key") {
+        let code = attribute(key, "code")?
+            .parse::<u16>()
+            .map_err(|e| format!("invalid key code: {}", e))?;
+        keys.retain(|(existing, _)| *existing != code);
+        keys.push((code, key));
+    }
+    Ok(keys)
+}
+
+fn elements<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    node.children()
+        .filter(move |child| child.has_tag_name(name))
+}
+
+fn attribute<'a>(node: Node<'a, '_>, name: &str) -> Result<&'a str, String> {
+    node.attribute(name)
+        .ok_or_else(|| format!("<{}> has no {}", node.tag_name().name(), name))
+}
+
+fn single_char(output: &str) -> Option<char> {
+    let mut chars = output.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+// macOS virtual key codes, named after the US keys, to HID usage IDs. Like on a Mac keyboard,
+// the key left of 1 is kVK_ANSI_Grave and the Non-US \ key is kVK_ISO_Section. See the kVK_
+// constants in HIToolbox's Events.h.
+fn usage_for_key_code(code: u16) -> Option<u8> {
+    const USAGES: [u8; 51] = [
+        0x04, 0x16, 0x07, 0x09, 0x0B, 0x0A, 0x1D, 0x1B, 0x06, 0x19, 0x64, 0x05, 0x14, 0x1A, 0x08,
+        0x15, 0x1C, 0x17, 0x1E, 0x1F, 0x20, 0x21, 0x23, 0x22, 0x2E, 0x26, 0x24, 0x2D, 0x25, 0x27,
+        0x30, 0x12, 0x18, 0x2F, 0x0C, 0x13, 0x00, 0x0F, 0x0D, 0x34, 0x0E, 0x33, 0x31, 0x36, 0x38,
+        0x11, 0x10, 0x37, 0x00, 0x2C, 0x35,
+    ];
+
+    // Return and Tab type the same on every layout
+    USAGES
+        .get(usize::from(code))
+        .copied()
+        .filter(|usage| *usage != 0)
+}
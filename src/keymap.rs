@@ -0,0 +1,177 @@
+use gen_layouts_sys::*;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{
+    deadkey_for_keycode, key_for_keycode, keycode_for_unicode, modifier_for_keycode, Chord,
+    ChordKey, Keycode, UNICODE_ENTER, UNICODE_FIRST_ASCII, UNICODE_FIRST_LATIN1,
+    UNICODE_LAST_ASCII, UNICODE_LAST_LATIN1, UNICODE_TAB,
+};
+
+use std::collections::HashMap;
+
+const LEFT_SHIFT: u8 = 0x02;
+const RIGHT_SHIFT: u8 = 0x20;
+
+// A key usage and the modifier byte it is pressed with
+pub(crate) type KeyPress = (u8, u8);
+
+/// What pressing a key with a combination of modifiers types on a layout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyChars {
+    /// The usage ID of the key, see `Key::usage`
+    pub usage: u8,
+    /// The modifier byte of the HID packet, see `Modifier::bits`
+    pub modifier: u8,
+    /// Every character the layout types with this key press, the one listed first in the layout
+    /// header first. Characters sharing a key press, e.g. a space and a no-break space, are
+    /// indistinguishable to the host.
+    pub chars: Vec<char>,
+    /// The accent of the deadkey on this key press, which types nothing until the next key
+    pub deadkey: Option<Accent>,
+}
+
+impl KeyChars {
+    /// The chord that presses the key with the modifiers, `None` if the usage has no `Key`
+    pub fn chord(&self) -> Option<Chord> {
+        let key = Key::ALL.iter().find(|key| key.usage() == self.usage)?;
+        let mut modifiers: Vec<Modifier> = Vec::new();
+        // The aliases for the left modifiers, e.g. Shift, come first
+        for modifier in Modifier::ALL.iter() {
+            if self.modifier & modifier.bits() > 0
+                && !modifiers.iter().any(|m| m.bits() == modifier.bits())
+            {
+                modifiers.push(*modifier);
+            }
+        }
+
+        Some(Chord {
+            modifiers,
+            key: Some(ChordKey::Key(*key)),
+        })
+    }
+}
+
+// The characters each key press types on a layout, the reverse of `keycode_for_unicode`
+pub(crate) struct KeyMap {
+    chars: HashMap<KeyPress, Vec<char>>,
+    // Characters typed by pressing a deadkey and then another key
+    composed: HashMap<(KeyPress, KeyPress), char>,
+    deadkeys: HashMap<KeyPress, Accent>,
+}
+
+impl KeyMap {
+    pub(crate) fn new(layout: &Layout) -> KeyMap {
+        let deadkeys = layout
+            .deadkeys
+            .iter()
+            .map(|deadkey| (key_press(layout, deadkey.keycode), deadkey.accent))
+            .collect();
+        let mut chars = HashMap::new();
+        let mut composed = HashMap::new();
+
+        // Other control characters are typed with Ctrl, which applications take as a shortcut
+        let unicodes = vec![UNICODE_ENTER, UNICODE_TAB]
+            .into_iter()
+            .chain(UNICODE_FIRST_ASCII..=UNICODE_LAST_ASCII)
+            .chain(UNICODE_FIRST_LATIN1..=UNICODE_LAST_LATIN1)
+            .chain(layout.extra_keycodes.iter().map(|(u, _)| u32::from(*u)));
+        for (unicode, c) in unicodes.filter_map(|u| std::char::from_u32(u).map(|c| (u, c))) {
+            let press = match keycode_for_unicode(layout, unicode) {
+                Keycode::RegularKey(keycode) => match deadkey_for_keycode(layout, keycode) {
+                    Some(dead_keycode) => {
+                        let presses = (key_press(layout, dead_keycode), key_press(layout, keycode));
+                        composed.entry(presses).or_insert(c);
+                        continue;
+                    }
+                    None => key_press(layout, keycode),
+                },
+                Keycode::ModifierKeySequence(..) | Keycode::InvalidCharacter => continue,
+            };
+            // Some headers define ASCII characters as 0, which presses nothing
+            if press.0 != 0 {
+                chars.entry(press).or_insert_with(Vec::new).push(c);
+            }
+        }
+
+        KeyMap {
+            chars,
+            composed,
+            deadkeys,
+        }
+    }
+
+    // The first character wins when several are typed the same way
+    pub(crate) fn char_for(&self, press: KeyPress) -> Option<char> {
+        self.chars
+            .get(&press)
+            .and_then(|chars| chars.first().copied())
+    }
+
+    pub(crate) fn is_deadkey(&self, press: KeyPress) -> bool {
+        self.deadkeys.contains_key(&press)
+    }
+
+    pub(crate) fn accent(&self, deadkey: KeyPress) -> Option<Accent> {
+        self.deadkeys.get(&deadkey).copied()
+    }
+
+    // The character the layout lists for the deadkey and key, otherwise the key's character with
+    // the deadkey's accent if Unicode has it precomposed, e.g. 'ǹ'
+    pub(crate) fn compose(&self, deadkey: KeyPress, press: KeyPress) -> Option<char> {
+        if let Some(c) = self.composed.get(&(deadkey, press)) {
+            return Some(*c);
+        }
+
+        let accent = self.deadkeys.get(&deadkey)?;
+        let base = self.char_for(press)?;
+        let mut composed = std::iter::once(base)
+            .chain(std::iter::once(accent.combining_char()))
+            .nfc();
+        match (composed.next(), composed.next()) {
+            (Some(c), None) => Some(c),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn key_chars(&self, (usage, modifier): KeyPress) -> KeyChars {
+        let press = (usage, normalise_shift(modifier));
+        KeyChars {
+            usage,
+            modifier,
+            chars: self.chars.get(&press).cloned().unwrap_or_default(),
+            deadkey: self.deadkeys.get(&press).copied(),
+        }
+    }
+
+    // Every key press that types a character or is a deadkey, by usage and then modifiers
+    pub(crate) fn all_key_chars(&self) -> Vec<KeyChars> {
+        let mut presses = self
+            .chars
+            .keys()
+            .chain(self.deadkeys.keys())
+            .copied()
+            .collect::<Vec<KeyPress>>();
+        presses.sort_unstable();
+        presses.dedup();
+        presses
+            .into_iter()
+            .map(|press| self.key_chars(press))
+            .collect()
+    }
+}
+
+// Either shift key types the same characters
+pub(crate) fn normalise_shift(modifier: u8) -> u8 {
+    if modifier & RIGHT_SHIFT > 0 {
+        (modifier & !RIGHT_SHIFT) | LEFT_SHIFT
+    } else {
+        modifier
+    }
+}
+
+fn key_press(layout: &Layout, keycode: u16) -> KeyPress {
+    (
+        key_for_keycode(layout, keycode),
+        modifier_for_keycode(layout, keycode),
+    )
+}
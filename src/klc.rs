@@ -0,0 +1,209 @@
+use gen_layouts_sys::{Accent, Layout};
+
+use crate::import::{LayoutBuilder, Level};
+use crate::Error;
+
+use std::fs;
+use std::path::Path;
+
+const UTF16_LE_BOM: [u8; 2] = [0xFF, 0xFE];
+const UTF16_BE_BOM: [u8; 2] = [0xFE, 0xFF];
+// The LAYOUT columns before the first shift state: scancode, virtual key and Caps Lock behaviour
+const LAYOUT_KEY_COLUMNS: usize = 3;
+const NO_CHARACTER: &str = "-1";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Section {
+    Other,
+    ShiftState,
+    Layout,
+    DeadKey(Option<Accent>),
+}
+
+/// Load a layout from a Microsoft Keyboard Layout Creator `.klc` file, which is UTF-16 encoded.
+/// The SHIFTSTATE columns for no modifiers, Shift, Ctrl+Alt and Shift+Ctrl+Alt are typed with
+/// none, Shift, AltGr and Shift+AltGr. Deadkeys type exactly what their DEADKEY section lists.
+pub fn load_klc_layout<P: AsRef<Path>>(path: P) -> Result<Layout, Error> {
+    let path = path.as_ref();
+    let invalid_file =
+        |message: String| Error::InvalidLayoutFile(format!("{}: {}", path.display(), message));
+
+    let bytes = fs::read(path).map_err(|e| invalid_file(e.to_string()))?;
+    let contents = decode(&bytes).ok_or_else(|| invalid_file("invalid UTF-16".to_string()))?;
+    parse(&contents)
+        .map_err(|(line, message)| invalid_file(format!("line {}: {}", line, message)))?
+        .build()
+        .map_err(invalid_file)
+}
+
+// Files saved by MSKLC are UTF-16 with a byte order mark, those edited by hand may be UTF-8
+fn decode(bytes: &[u8]) -> Option<String> {
+    let units = |from_bytes: fn([u8; 2]) -> u16| {
+        bytes[2..]
+            .chunks(2)
+            .map(|unit| from_bytes([unit[0], *unit.get(1).unwrap_or(&0)]))
+            .collect::<Vec<u16>>()
+    };
+
+    match bytes.get(..2) {
+        Some(bom) if bom == UTF16_LE_BOM => String::from_utf16(&units(u16::from_le_bytes)).ok(),
+        Some(bom) if bom == UTF16_BE_BOM => String::from_utf16(&units(u16::from_be_bytes)).ok(),
+        _ => String::from_utf8(bytes.to_vec()).ok(),
+    }
+}
+
+fn parse(contents: &str) -> Result<LayoutBuilder, (usize, String)> {
+    let mut builder = LayoutBuilder::default();
+    let mut section = Section::Other;
+    // The level of each LAYOUT column after the first three, `None` for those with Ctrl alone
+    let mut levels: Vec<Option<Level>> = Vec::new();
+
+    for (idx, line) in contents.lines().enumerate() {
+        let line_number = idx + 1;
+        let line = line.split("//").next().unwrap_or_default();
+        let columns = line.split_whitespace().collect::<Vec<&str>>();
+        let first = match columns.first() {
+            Some(first) => *first,
+            None => continue,
+        };
+
+        match first {
+            "SHIFTSTATE" => section = Section::ShiftState,
+            "LAYOUT" => section = Section::Layout,
+            "DEADKEY" => {
+                let c = columns
+                    .get(1)
+                    .and_then(|value| parse_char(value))
+                    .ok_or_else(|| {
+                        (
+                            line_number,
+                            "expected a character after DEADKEY".to_string(),
+                        )
+                    })?;
+                section = Section::DeadKey(Accent::from_char(c));
+            }
+            "KBD" | "COPYRIGHT" | "COMPANY" | "LOCALENAME" | "LOCALEID" | "VERSION"
+            | "LIGATURE" | "KEYNAME" | "KEYNAME_EXT" | "KEYNAME_DEAD" | "DESCRIPTIONS"
+            | "LANGUAGENAMES" | "ATTRIBUTES" | "ENDKBD" => section = Section::Other,
+            _ => match section {
+                Section::ShiftState => {
+                    let state = first
+                        .parse::<u8>()
+                        .map_err(|_| (line_number, format!("invalid shift state {}", first)))?;
+                    levels.push(level_for_shift_state(state));
+                }
+                Section::Layout => {
+                    // Lines for SGCap keys have no scancode
+                    let usage = match u8::from_str_radix(first, 16)
+                        .ok()
+                        .and_then(usage_for_scancode)
+                    {
+                        Some(usage) => usage,
+                        None => continue,
+                    };
+                    for (value, level) in columns.iter().skip(LAYOUT_KEY_COLUMNS).zip(levels.iter())
+                    {
+                        let (value, dead) = match value.strip_suffix('@') {
+                            Some(value) => (value, true),
+                            None => (*value, false),
+                        };
+                        match (parse_char(value), level, dead) {
+                            (Some(c), Some(level), false) => builder.add_char(c, usage, *level),
+                            (Some(c), Some(level), true) => {
+                                if let Some(accent) = Accent::from_char(c) {
+                                    builder.add_deadkey(accent, usage, *level);
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                // Deadkeys for accents a `Layout` has no `Accent` for are skipped
+                Section::DeadKey(Some(accent)) => {
+                    if let (Some(base), Some(composed)) = (
+                        parse_char(first),
+                        columns.get(1).and_then(|value| parse_char(value)),
+                    ) {
+                        builder.add_composed(accent, base, composed);
+                    }
+                }
+                Section::DeadKey(None) | Section::Other => {}
+            },
+        }
+    }
+
+    Ok(builder)
+}
+
+// Characters are written as UTF-16 code units in hex, e.g. 00e4, or as themselves, e.g. q.
+// -1 is no character and %% a ligature.
+fn parse_char(value: &str) -> Option<char> {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ if value == NO_CHARACTER => None,
+        _ => u32::from_str_radix(value, 16)
+            .ok()
+            .and_then(std::char::from_u32),
+    }
+}
+
+// Shift states are a bitmask of Shift (1), Ctrl (2) and Alt (4). Windows types Ctrl+Alt like AltGr.
+fn level_for_shift_state(state: u8) -> Option<Level> {
+    match state {
+        0 => Some(Level::Base),
+        1 => Some(Level::Shift),
+        6 => Some(Level::AltGr),
+        7 => Some(Level::ShiftAltGr),
+        _ => None,
+    }
+}
+
+// Scan code set 1, the one KLC files use, to HID usage IDs, from Microsoft's USB HID to PS/2 Scan
+// Code Translation Table
+fn usage_for_scancode(scancode: u8) -> Option<u8> {
+    let usage = match scancode {
+        0x02..=0x0B => 0x1E + (scancode - 0x02),
+        0x0C => 0x2D,
+        0x0D => 0x2E,
+        0x10 => 0x14,
+        0x11 => 0x1A,
+        0x12 => 0x08,
+        0x13 => 0x15,
+        0x14 => 0x17,
+        0x15 => 0x1C,
+        0x16 => 0x18,
+        0x17 => 0x0C,
+        0x18 => 0x12,
+        0x19 => 0x13,
+        0x1A => 0x2F,
+        0x1B => 0x30,
+        0x1E => 0x04,
+        0x1F => 0x16,
+        0x20 => 0x07,
+        0x21 => 0x09,
+        0x22 => 0x0A,
+        0x23 => 0x0B,
+        0x24 => 0x0D,
+        0x25 => 0x0E,
+        0x26 => 0x0F,
+        0x27 => 0x33,
+        0x28 => 0x34,
+        0x29 => 0x35,
+        0x2B => 0x31,
+        0x2C => 0x1D,
+        0x2D => 0x1B,
+        0x2E => 0x06,
+        0x2F => 0x19,
+        0x30 => 0x05,
+        0x31 => 0x11,
+        0x32 => 0x10,
+        0x33 => 0x36,
+        0x34 => 0x37,
+        0x35 => 0x38,
+        0x39 => 0x2C,
+        0x56 => 0x64,
+        _ => return None,
+    };
+    Some(usage)
+}
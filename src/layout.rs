@@ -0,0 +1,340 @@
+use bytes::Bytes;
+use gen_layouts_sys::*;
+
+use crate::decode::decode;
+use crate::keymap::KeyMap;
+use crate::pack::packed_hid_packets;
+use crate::transliterate::transliterate;
+use crate::{
+    keys_and_modifiers_to_hid_packets, nfc_char_indices, push_char, push_named_key, Alteration,
+    Chord, Device, EncodeOptions, Encoding, Error, Fallback, Input, KeyChars, KeyMod, Untypeable,
+};
+
+/// Typing strings with a layout that has already been resolved, e.g. `LayoutId::German.layout()`
+pub trait LayoutExt {
+    /// Check that every character of the string can be typed. Fails with
+    /// `Error::InvalidCharacters` listing all the characters that cannot and their byte offsets.
+    fn check(&self, string: &str) -> Result<(), Error>;
+
+    /// Get the key and modifier pairs required to type the string, applying the options to any
+    /// character the layout cannot type
+    fn keys_and_modifiers(&self, string: &str, options: EncodeOptions) -> Result<Encoding, Error>;
+
+    /// Get the text the HID packets type, see `hid_packets_to_string`
+    fn decode(&self, packets: &[u8]) -> Result<String, Error>;
+
+    /// Get what pressing the key with the usage ID and modifier byte types, see
+    /// `key_and_modifier_to_chars`
+    fn key_chars(&self, usage: u8, modifier: u8) -> KeyChars;
+
+    /// Get every key and modifier combination that types a character or is a deadkey, ordered by
+    /// usage ID and then modifier byte. Lists the characters on each key at each shift level.
+    fn all_key_chars(&self) -> Vec<KeyChars>;
+
+    /// Get the key and modifier pairs that press the chord
+    fn chord_keys_and_modifiers(&self, chord: &Chord) -> Result<Vec<KeyMod>, Error>;
+
+    /// Create the HID packets required to type the string
+    fn encode(&self, string: &str) -> Result<Bytes, Error> {
+        self.encode_with_options(string, EncodeOptions::default())
+            .map(|(packets, _)| packets)
+    }
+
+    /// Like `encode` but applies the options to any character the layout cannot type. Also
+    /// returns every character that was skipped or replaced.
+    fn encode_with_options(
+        &self,
+        string: &str,
+        options: EncodeOptions,
+    ) -> Result<(Bytes, Vec<Alteration>), Error> {
+        let Encoding {
+            keys_and_modifiers,
+            alterations,
+        } = self.keys_and_modifiers(string, options)?;
+
+        debug!("Keys and Modifiers for {}:{:?}", string, keys_and_modifiers);
+
+        Ok((
+            keys_and_modifiers_to_hid_packets(&keys_and_modifiers),
+            alterations,
+        ))
+    }
+
+    /// Like `encode` but presses several keys per packet where the host still types them in the
+    /// same order, see `string_to_packed_hid_packets`
+    fn encode_packed(&self, string: &str) -> Result<Bytes, Error> {
+        self.encode_packed_with_options(string, EncodeOptions::default())
+            .map(|(packets, _)| packets)
+    }
+
+    /// Like `encode_packed` but applies the options to any character the layout cannot type
+    fn encode_packed_with_options(
+        &self,
+        string: &str,
+        options: EncodeOptions,
+    ) -> Result<(Bytes, Vec<Alteration>), Error>;
+
+    /// Get the key and modifier pairs required to type text mixed with named keys. The index of
+    /// each alteration is the byte offset within all the text inputs joined together. Fails on
+    /// media and system keys as they are not sent in keyboard reports, see
+    /// `encode_inputs_for_device`.
+    fn inputs_keys_and_modifiers(
+        &self,
+        inputs: &[Input],
+        options: EncodeOptions,
+    ) -> Result<Encoding, Error> {
+        let mut keys_and_modifiers = Vec::new();
+        let mut alterations = Vec::new();
+        let mut text_offset = 0;
+
+        for input in inputs {
+            match input {
+                Input::Text(text) => {
+                    let encoding = self.keys_and_modifiers(text, options)?;
+                    keys_and_modifiers.extend(encoding.keys_and_modifiers);
+                    alterations.extend(encoding.alterations.into_iter().map(|mut alteration| {
+                        alteration.index += text_offset;
+                        alteration
+                    }));
+                    text_offset += text.len();
+                }
+                Input::Key(key) => push_named_key(*key, &mut keys_and_modifiers),
+                Input::Chord(chord) => {
+                    keys_and_modifiers.extend(self.chord_keys_and_modifiers(chord)?)
+                }
+                Input::Media(media_key) => {
+                    return Err(Error::UnsupportedByDevice(media_key.to_string()))
+                }
+                Input::System(system_key) => {
+                    return Err(Error::UnsupportedByDevice(system_key.to_string()))
+                }
+            }
+        }
+
+        Ok(Encoding {
+            keys_and_modifiers,
+            alterations,
+        })
+    }
+
+    /// Create the HID packets that press the chord
+    fn encode_chord(&self, chord: &Chord) -> Result<Bytes, Error> {
+        Ok(keys_and_modifiers_to_hid_packets(
+            &self.chord_keys_and_modifiers(chord)?,
+        ))
+    }
+
+    /// Create the HID packets required to type text mixed with named keys
+    fn encode_inputs(&self, inputs: &[Input]) -> Result<Bytes, Error> {
+        self.encode_inputs_with_options(inputs, EncodeOptions::default())
+            .map(|(packets, _)| packets)
+    }
+
+    /// Like `encode_inputs` but applies the options to any character the layout cannot type
+    fn encode_inputs_with_options(
+        &self,
+        inputs: &[Input],
+        options: EncodeOptions,
+    ) -> Result<(Bytes, Vec<Alteration>), Error> {
+        let Encoding {
+            keys_and_modifiers,
+            alterations,
+        } = self.inputs_keys_and_modifiers(inputs, options)?;
+
+        debug!(
+            "Keys and Modifiers for {:?}:{:?}",
+            inputs, keys_and_modifiers
+        );
+
+        Ok((
+            keys_and_modifiers_to_hid_packets(&keys_and_modifiers),
+            alterations,
+        ))
+    }
+
+    /// Create the reports required to type text mixed with named, media and system keys on the
+    /// device.
+    /// Each report is written to the device on its own.
+    fn encode_inputs_for_device(
+        &self,
+        inputs: &[Input],
+        device: Device,
+        options: EncodeOptions,
+    ) -> Result<(Vec<Bytes>, Vec<Alteration>), Error> {
+        let mut reports = Vec::new();
+        let mut alterations = Vec::new();
+        let mut text_offset = 0;
+
+        // Media and system keys have their own reports, everything between them is typed on the
+        // keyboard
+        for (keyboard_inputs, control_input) in split_control_inputs(inputs) {
+            let encoding = self.inputs_keys_and_modifiers(keyboard_inputs, options)?;
+            let packets = keys_and_modifiers_to_hid_packets(&encoding.keys_and_modifiers);
+            reports.extend(device.keyboard_reports(&packets)?);
+            alterations.extend(encoding.alterations.into_iter().map(|mut alteration| {
+                alteration.index += text_offset;
+                alteration
+            }));
+            text_offset += keyboard_inputs
+                .iter()
+                .map(|input| match input {
+                    Input::Text(text) => text.len(),
+                    _ => 0,
+                })
+                .sum::<usize>();
+
+            match control_input {
+                Some(Input::Media(media_key)) => {
+                    reports.extend(device.media_key_reports(*media_key)?)
+                }
+                Some(Input::System(system_key)) => {
+                    reports.extend(device.system_key_reports(*system_key)?)
+                }
+                _ => {}
+            }
+        }
+
+        Ok((reports, alterations))
+    }
+}
+
+impl LayoutExt for Layout {
+    fn encode_packed_with_options(
+        &self,
+        string: &str,
+        options: EncodeOptions,
+    ) -> Result<(Bytes, Vec<Alteration>), Error> {
+        let Encoding {
+            keys_and_modifiers,
+            alterations,
+        } = self.keys_and_modifiers(string, options)?;
+
+        Ok((packed_hid_packets(self, &keys_and_modifiers), alterations))
+    }
+
+    fn chord_keys_and_modifiers(&self, chord: &Chord) -> Result<Vec<KeyMod>, Error> {
+        chord.keys_and_modifiers(self)
+    }
+
+    fn decode(&self, packets: &[u8]) -> Result<String, Error> {
+        decode(self, packets)
+    }
+
+    fn key_chars(&self, usage: u8, modifier: u8) -> KeyChars {
+        KeyMap::new(self).key_chars((usage, modifier))
+    }
+
+    fn all_key_chars(&self) -> Vec<KeyChars> {
+        KeyMap::new(self).all_key_chars()
+    }
+
+    fn check(&self, string: &str) -> Result<(), Error> {
+        let mut keys_and_modifiers = Vec::new();
+        let invalid_characters = nfc_char_indices(string)
+            .into_iter()
+            .filter(|(_, c)| {
+                keys_and_modifiers.clear();
+                push_char(self, *c, Fallback::None, &mut keys_and_modifiers).is_err()
+            })
+            .collect::<Vec<(usize, char)>>();
+
+        if invalid_characters.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::InvalidCharacters(invalid_characters))
+        }
+    }
+
+    fn keys_and_modifiers(&self, string: &str, options: EncodeOptions) -> Result<Encoding, Error> {
+        let mut keys_and_modifiers: Vec<KeyMod> = Vec::with_capacity(string.len());
+        let mut alterations = Vec::new();
+
+        for (index, c) in nfc_char_indices(string) {
+            match push_char(self, c, options.fallback, &mut keys_and_modifiers) {
+                Err(Error::InvalidCharacter(_)) => {}
+                result => {
+                    result?;
+                    continue;
+                }
+            }
+
+            let replacement = match options.untypeable {
+                Untypeable::Error => return Err(Error::InvalidCharacter(c)),
+                Untypeable::Skip => None,
+                Untypeable::Replace(replacement) => Some(replacement.to_string()),
+                Untypeable::Transliterate => {
+                    Some(transliterate(c).ok_or(Error::InvalidCharacter(c))?)
+                }
+            };
+
+            if let Some(ref replacement) = replacement {
+                for replacement_c in replacement.chars() {
+                    push_char(
+                        self,
+                        replacement_c,
+                        options.fallback,
+                        &mut keys_and_modifiers,
+                    )?;
+                }
+            }
+
+            alterations.push(Alteration {
+                index,
+                original: c,
+                replacement,
+            });
+        }
+
+        Ok(Encoding {
+            keys_and_modifiers,
+            alterations,
+        })
+    }
+}
+
+impl LayoutExt for LayoutId {
+    fn encode_packed_with_options(
+        &self,
+        string: &str,
+        options: EncodeOptions,
+    ) -> Result<(Bytes, Vec<Alteration>), Error> {
+        self.layout().encode_packed_with_options(string, options)
+    }
+
+    fn chord_keys_and_modifiers(&self, chord: &Chord) -> Result<Vec<KeyMod>, Error> {
+        self.layout().chord_keys_and_modifiers(chord)
+    }
+
+    fn check(&self, string: &str) -> Result<(), Error> {
+        self.layout().check(string)
+    }
+
+    fn decode(&self, packets: &[u8]) -> Result<String, Error> {
+        self.layout().decode(packets)
+    }
+
+    fn key_chars(&self, usage: u8, modifier: u8) -> KeyChars {
+        self.layout().key_chars(usage, modifier)
+    }
+
+    fn all_key_chars(&self) -> Vec<KeyChars> {
+        self.layout().all_key_chars()
+    }
+
+    fn keys_and_modifiers(&self, string: &str, options: EncodeOptions) -> Result<Encoding, Error> {
+        self.layout().keys_and_modifiers(string, options)
+    }
+}
+
+fn split_control_inputs(inputs: &[Input]) -> Vec<(&[Input], Option<&Input>)> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    for (idx, input) in inputs.iter().enumerate() {
+        if let Input::Media(_) | Input::System(_) = input {
+            runs.push((&inputs[start..idx], Some(input)));
+            start = idx + 1;
+        }
+    }
+    runs.push((&inputs[start..], None));
+    runs
+}
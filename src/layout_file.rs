@@ -0,0 +1,49 @@
+use gen_layouts_sys::Layout;
+
+use crate::validate::validate;
+use crate::Error;
+
+use std::fs;
+use std::path::Path;
+
+/// Parse a layout described in TOML, see the README for the format
+pub fn layout_from_toml(toml: &str) -> Result<Layout, Error> {
+    let layout = toml::from_str(toml).map_err(|e| Error::InvalidLayoutFile(format!("{}", e)))?;
+    validate(layout).map_err(Error::InvalidLayoutFile)
+}
+
+/// Parse a layout described in JSON, see the README for the format
+pub fn layout_from_json(json: &str) -> Result<Layout, Error> {
+    let layout =
+        serde_json::from_str(json).map_err(|e| Error::InvalidLayoutFile(format!("{}", e)))?;
+    validate(layout).map_err(Error::InvalidLayoutFile)
+}
+
+/// Describe the layout in TOML, the format read by `layout_from_toml`
+pub fn layout_to_toml(layout: &Layout) -> Result<String, Error> {
+    toml::to_string(layout).map_err(|e| Error::InvalidLayoutFile(format!("{}", e)))
+}
+
+/// Describe the layout in JSON, the format read by `layout_from_json`
+pub fn layout_to_json(layout: &Layout) -> Result<String, Error> {
+    serde_json::to_string_pretty(layout).map_err(|e| Error::InvalidLayoutFile(format!("{}", e)))
+}
+
+/// Load a layout from a `.toml` or `.json` file
+pub fn load_layout<P: AsRef<Path>>(path: P) -> Result<Layout, Error> {
+    let path = path.as_ref();
+    let invalid_file =
+        |message: String| Error::InvalidLayoutFile(format!("{}: {}", path.display(), message));
+
+    let contents = fs::read_to_string(path).map_err(|e| invalid_file(e.to_string()))?;
+    let layout = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("toml") => layout_from_toml(&contents),
+        Some("json") => layout_from_json(&contents),
+        _ => return Err(invalid_file("expected a .toml or .json file".to_string())),
+    };
+
+    layout.map_err(|e| match e {
+        Error::InvalidLayoutFile(message) => invalid_file(message),
+        e => e,
+    })
+}
@@ -1,18 +1,69 @@
 #[macro_use]
 extern crate log;
 
+mod chord;
+mod decode;
+mod ducky;
+mod fallback;
+mod format;
+mod import;
+mod input;
+mod keylayout;
+mod keymap;
+mod keysyms;
+mod klc;
+mod layout;
+#[cfg(feature = "layout-files")]
+mod layout_file;
+mod options;
+mod pack;
+mod registry;
+mod report;
+mod script;
+mod transliterate;
+mod validate;
+mod xkb;
+
 use bytes::{BufMut, Bytes, BytesMut};
 use gen_layouts_sys::*;
+use unicode_normalization::char::canonical_combining_class;
+use unicode_normalization::{is_nfc, UnicodeNormalization};
 
 use std::fmt;
 
-const UNICODE_ENTER: u16 = 10; // \n
-const UNICODE_TAB: u16 = 9; // \t
+pub use crate::chord::{Chord, ChordKey};
+pub use crate::fallback::Fallback;
+pub use crate::format::{KeyRollover, ReportFormat};
+pub use crate::input::Input;
+pub use crate::keylayout::load_keylayout;
+pub use crate::keymap::KeyChars;
+pub use crate::klc::load_klc_layout;
+pub use crate::layout::LayoutExt;
+#[cfg(feature = "layout-files")]
+pub use crate::layout_file::{
+    layout_from_json, layout_from_toml, layout_to_json, layout_to_toml, load_layout,
+};
+pub use crate::options::{Alteration, EncodeOptions, Encoding, Untypeable};
+pub use crate::registry::register_layout;
+pub use crate::report::{
+    Device, COMPOSITE_REPORT_DESCRIPTOR, CONSUMER_REPORT_ID, KEYBOARD_REPORT_DESCRIPTOR,
+    KEYBOARD_REPORT_ID, SYSTEM_CONTROL_REPORT_DESCRIPTOR, SYSTEM_REPORT_ID,
+};
+pub use crate::script::{Script, Step};
+pub use crate::xkb::load_xkb_layout;
+pub use gen_layouts_sys::{
+    Accent, DeadKey, InvalidKey, InvalidLayoutId, InvalidMediaKey, InvalidModifier,
+    InvalidSystemKey, Key, Layout, LayoutId, MediaKey, Modifier, SystemKey,
+};
+
+const UNICODE_ENTER: u32 = 10; // \n
+const UNICODE_TAB: u32 = 9; // \t
 // https://stackoverflow.com/questions/23320417/what-is-this-character-separator
-const CONTROL_CHARACTER_OFFSET: u16 = 0x40;
-const UNICODE_FIRST_ASCII: u16 = 0x20; // SPACE
-const UNICODE_LAST_ASCII: u16 = 0x7F; // BACKSPACE
-const UNICODE_DIGIT_OFFSET: usize = 48; // 0
+const CONTROL_CHARACTER_OFFSET: u32 = 0x40;
+const UNICODE_FIRST_ASCII: u32 = 0x20; // SPACE
+const UNICODE_LAST_ASCII: u32 = 0x7F; // BACKSPACE
+const UNICODE_FIRST_LATIN1: u32 = 0xA0; // NO-BREAK SPACE
+const UNICODE_LAST_LATIN1: u32 = 0xFF; // LATIN SMALL LETTER Y WITH DIAERESIS
 const KEY_MASK: u16 = 0x3F; // Remove SHIFT/ALT/CTRL from keycode
 /// The number of bytes in a keyboard HID packet
 pub const HID_PACKET_LEN: usize = 8;
@@ -23,6 +74,33 @@ const RELEASE_KEYS_HID_PACKET: [u8; 8] = [0u8; 8];
 pub enum Error {
     InvalidLayoutKey(String),
     InvalidCharacter(char),
+    /// Every character that cannot be typed along with its byte offset in the string
+    InvalidCharacters(Vec<(usize, char)>),
+    InvalidFallback(String),
+    InvalidUntypeable(String),
+    InvalidKey(String),
+    InvalidChord(String),
+    InvalidDevice(String),
+    InvalidReportFormat(String),
+    /// A layout data file that cannot be read or parsed
+    InvalidLayoutFile(String),
+    /// A layout key that is already taken when registering a layout
+    DuplicateLayout(String),
+    /// A key the device has no report for, e.g. a media or system key on a boot keyboard
+    UnsupportedByDevice(String),
+    InvalidScript {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// HID packets are 8 bytes each
+    InvalidPacketLength(usize),
+    /// A key press in the packet with the given index that the layout types nothing with
+    UnknownKeyPress {
+        packet: usize,
+        key: u8,
+        modifier: u8,
+    },
 }
 
 #[derive(Debug)]
@@ -38,91 +116,234 @@ pub struct KeyMod {
     pub key: u8,
     pub modifier: u8,
     pub release: Release,
+    /// The fallback that produced this key press, `None` when the layout maps the character
+    pub fallback: Option<Fallback>,
 }
 
-enum Keycode {
+pub(crate) enum Keycode {
     ModifierKeySequence(u16, Vec<u16>),
     RegularKey(u16),
     InvalidCharacter,
 }
 
+impl From<InvalidLayoutId> for Error {
+    fn from(InvalidLayoutId(key): InvalidLayoutId) -> Error {
+        Error::InvalidLayoutKey(key)
+    }
+}
+
+impl From<InvalidKey> for Error {
+    fn from(InvalidKey(key): InvalidKey) -> Error {
+        Error::InvalidKey(key)
+    }
+}
+
+impl From<InvalidMediaKey> for Error {
+    fn from(InvalidMediaKey(key): InvalidMediaKey) -> Error {
+        Error::InvalidKey(key)
+    }
+}
+
+impl From<InvalidSystemKey> for Error {
+    fn from(InvalidSystemKey(key): InvalidSystemKey) -> Error {
+        Error::InvalidKey(key)
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Error::InvalidLayoutKey(key) => write!(f, "No layout defined for {}", key),
             Error::InvalidCharacter(c) => write!(f, "Invalid character: '{}' or [{:?}]", c, c),
+            Error::InvalidCharacters(characters) => {
+                write!(f, "Invalid characters:")?;
+                for (index, c) in characters {
+                    write!(f, " '{}' or [{:?}] at byte {}", c, c, index)?;
+                }
+                Ok(())
+            }
+            Error::InvalidFallback(fallback) => write!(f, "Unknown fallback: {}", fallback),
+            Error::InvalidUntypeable(untypeable) => {
+                write!(f, "Unknown untypeable policy: {}", untypeable)
+            }
+            Error::InvalidKey(key) => write!(f, "Unknown key: {}", key),
+            Error::InvalidChord(chord) => write!(f, "Invalid chord: {}", chord),
+            Error::InvalidDevice(device) => write!(f, "Unknown device: {}", device),
+            Error::InvalidReportFormat(format) => write!(f, "Unknown report format: {}", format),
+            Error::InvalidLayoutFile(message) => write!(f, "Invalid layout file: {}", message),
+            Error::DuplicateLayout(key) => write!(f, "A layout is already defined for {}", key),
+            Error::UnsupportedByDevice(key) => {
+                write!(f, "{} cannot be sent by this device", key)
+            }
+            Error::InvalidScript {
+                line,
+                column,
+                message,
+            } => write!(f, "Line {}, column {}: {}", line, column, message),
+            Error::InvalidPacketLength(len) => write!(
+                f,
+                "HID packets are {} bytes each, got {} bytes",
+                HID_PACKET_LEN, len
+            ),
+            Error::UnknownKeyPress {
+                packet,
+                key,
+                modifier,
+            } => write!(
+                f,
+                "Packet {}: key {:#04X} with modifiers {:#04X} types nothing on this layout",
+                packet, key, modifier
+            ),
         }
     }
 }
 
-/// Get a list of the supported keyboard layouts
+/// Get a list of the supported keyboard layouts, including those registered at runtime
 pub fn available_layouts() -> Vec<&'static str> {
-    LAYOUT_MAP.keys().map(|k| *k).collect()
+    LAYOUT_MAP
+        .keys()
+        .copied()
+        .chain(registry::registered_layout_keys())
+        .collect()
+}
+
+/// Get the layout for the key, built-in or registered at runtime, e.g. to use `LayoutExt` on it
+pub fn get_layout(layout_key: &str) -> Result<&'static Layout, Error> {
+    layout_for_key(layout_key)
+}
+
+/// Check that every character of the given string can be typed on a keyboard with the specified
+/// layout. Fails with `Error::InvalidCharacters` listing all the characters that cannot, along
+/// with their byte offsets in the string, e.g. 1, 4 and 6 for "aΩbü€" on a US layout.
+pub fn check_string(layout_key: &str, string: &str) -> Result<(), Error> {
+    layout_for_key(layout_key)?.check(string)
 }
 
 /// Get a list of the key and modifier pairs required to type the given string on a keyboard with
 /// the specified layout.
 pub fn string_to_keys_and_modifiers(layout_key: &str, string: &str) -> Result<Vec<KeyMod>, Error> {
-    let layout = LAYOUT_MAP
-        .get(layout_key)
-        .ok_or_else(|| Error::InvalidLayoutKey(layout_key.to_string()))?;
-
-    let mut keys_and_modifiers: Vec<KeyMod> = Vec::with_capacity(string.len());
-
-    for c in string.chars() {
-        match keycode_for_unicode(layout, c as u16) {
-            Keycode::ModifierKeySequence(modifier, sequence) => {
-                for keycode in sequence {
-                    keys_and_modifiers.push(KeyMod {
-                        key: keycode as u8,
-                        modifier: modifier as u8,
-                        release: Release::Keys,
-                    });
-                }
-                // Manually add release after sequence is finished
-                keys_and_modifiers.push(KeyMod {
-                    key: 0,
-                    modifier: 0,
-                    release: Release::None,
-                });
-            }
-            Keycode::RegularKey(keycode) => {
-                if let Some(dead_keycode) = deadkey_for_keycode(layout, keycode) {
-                    let key = key_for_keycode(layout, dead_keycode);
-                    let modifier = modifier_for_keycode(layout, dead_keycode);
-                    keys_and_modifiers.push(KeyMod {
-                        key,
-                        modifier,
-                        release: Release::All,
-                    });
-                }
-                let key = key_for_keycode(layout, keycode);
-                let modifier = modifier_for_keycode(layout, keycode);
-                keys_and_modifiers.push(KeyMod {
-                    key,
-                    modifier,
-                    release: Release::All,
-                });
-            }
-            _ => return Err(Error::InvalidCharacter(c)),
-        }
-    }
+    string_to_keys_and_modifiers_with_fallback(layout_key, string, Fallback::None)
+}
+
+/// Like `string_to_keys_and_modifiers` but characters the layout cannot type are entered using the
+/// given fallback instead of failing.
+pub fn string_to_keys_and_modifiers_with_fallback(
+    layout_key: &str,
+    string: &str,
+    fallback: Fallback,
+) -> Result<Vec<KeyMod>, Error> {
+    let options = EncodeOptions {
+        fallback,
+        ..EncodeOptions::default()
+    };
+    string_to_keys_and_modifiers_with_options(layout_key, string, options)
+        .map(|encoding| encoding.keys_and_modifiers)
+}
 
-    Ok(keys_and_modifiers)
+/// Get the key and modifier pairs required to type the given string, applying the options to any
+/// character the layout cannot type. Reports every character that was skipped or replaced.
+pub fn string_to_keys_and_modifiers_with_options(
+    layout_key: &str,
+    string: &str,
+    options: EncodeOptions,
+) -> Result<Encoding, Error> {
+    layout_for_key(layout_key)?.keys_and_modifiers(string, options)
 }
 
 /// Create the sequence of HID packets required to type the given string. Impersonating a keyboard
 /// with the specified layout. These packets can be written directly to a HID device file.
 pub fn string_to_hid_packets(layout_key: &str, string: &str) -> Result<Bytes, Error> {
-    let keys_and_modifiers = string_to_keys_and_modifiers(layout_key, string)?;
+    string_to_hid_packets_with_fallback(layout_key, string, Fallback::None)
+}
+
+/// Like `string_to_hid_packets` but characters the layout cannot type are entered using the given
+/// fallback instead of failing.
+pub fn string_to_hid_packets_with_fallback(
+    layout_key: &str,
+    string: &str,
+    fallback: Fallback,
+) -> Result<Bytes, Error> {
+    let options = EncodeOptions {
+        fallback,
+        ..EncodeOptions::default()
+    };
+    string_to_hid_packets_with_options(layout_key, string, options).map(|(packets, _)| packets)
+}
+
+/// Like `string_to_hid_packets` but applies the options to any character the layout cannot type.
+/// Also returns every character that was skipped or replaced.
+pub fn string_to_hid_packets_with_options(
+    layout_key: &str,
+    string: &str,
+    options: EncodeOptions,
+) -> Result<(Bytes, Vec<Alteration>), Error> {
+    layout_for_key(layout_key)?.encode_with_options(string, options)
+}
+
+/// Like `string_to_hid_packets` but presses up to 6 characters per packet where the host still
+/// types them in the same order: runs of distinct keys with the same modifiers, without deadkeys.
+/// Needs fewer packets for long strings.
+pub fn string_to_packed_hid_packets(layout_key: &str, string: &str) -> Result<Bytes, Error> {
+    layout_for_key(layout_key)?.encode_packed(string)
+}
+
+/// Like `string_to_packed_hid_packets` but applies the options to any character the layout cannot
+/// type. Also returns every character that was skipped or replaced.
+pub fn string_to_packed_hid_packets_with_options(
+    layout_key: &str,
+    string: &str,
+    options: EncodeOptions,
+) -> Result<(Bytes, Vec<Alteration>), Error> {
+    layout_for_key(layout_key)?.encode_packed_with_options(string, options)
+}
+
+/// Like `string_to_hid_packets` but renders the reports in the given format, e.g. an NKRO bitmap.
+/// Each report is `format.report_len()` bytes long.
+pub fn string_to_hid_packets_with_format(
+    layout_key: &str,
+    string: &str,
+    format: ReportFormat,
+) -> Result<Bytes, Error> {
+    format.render(&string_to_hid_packets(layout_key, string)?)
+}
+
+/// Get the text the HID packets type on a keyboard with the specified layout, the reverse of
+/// `string_to_hid_packets`. Fails on key presses the layout types nothing with, e.g. shortcuts.
+pub fn hid_packets_to_string(layout_key: &str, packets: &[u8]) -> Result<String, Error> {
+    layout_for_key(layout_key)?.decode(packets)
+}
+
+/// Get what pressing the key with the given usage ID and modifier byte types on a keyboard with the
+/// specified layout: its characters, including ISO-8859-1 ones, or the deadkey it is.
+pub fn key_and_modifier_to_chars(
+    layout_key: &str,
+    usage: u8,
+    modifier: u8,
+) -> Result<KeyChars, Error> {
+    Ok(layout_for_key(layout_key)?.key_chars(usage, modifier))
+}
+
+/// Create the HID packets required to type text mixed with named keys, e.g. to fill in a form and
+/// navigate between its fields.
+pub fn inputs_to_hid_packets(layout_key: &str, inputs: &[Input]) -> Result<Bytes, Error> {
+    layout_for_key(layout_key)?.encode_inputs(inputs)
+}
+
+/// Create the HID packets for a shortcut such as Ctrl+Z, pressing the key where the specified layout
+/// puts it
+pub fn chord_to_hid_packets(layout_key: &str, chord: &Chord) -> Result<Bytes, Error> {
+    layout_for_key(layout_key)?.encode_chord(chord)
+}
 
-    debug!("Keys and Modifiers for {}:{:?}", string, keys_and_modifiers);
+/// Create the HID packets for a list of key and modifier pairs
+pub fn keys_and_modifiers_to_hid_packets(keys_and_modifiers: &[KeyMod]) -> Bytes {
     let mut packet_bytes = BytesMut::with_capacity(HID_PACKET_LEN * keys_and_modifiers.len() * 2);
 
     for KeyMod {
         key,
         modifier,
         release,
+        ..
     } in keys_and_modifiers.iter()
     {
         packet_bytes.put_u8(*modifier);
@@ -141,66 +362,213 @@ pub fn string_to_hid_packets(layout_key: &str, string: &str) -> Result<Bytes, Er
         }
     }
 
-    Ok(packet_bytes.freeze())
+    packet_bytes.freeze()
 }
 
-fn keycode_for_unicode(layout: &Layout, unicode: u16) -> Keycode {
-    match unicode {
-        u if u == UNICODE_ENTER => Keycode::RegularKey(ENTER_KEYCODE & layout.keycode_mask),
-        u if u == UNICODE_TAB => Keycode::RegularKey(TAB_KEYCODE & layout.keycode_mask),
-        u if u < UNICODE_FIRST_ASCII => {
-            let idx = ((u + CONTROL_CHARACTER_OFFSET) - UNICODE_FIRST_ASCII) as usize;
-            let keycodes = vec![layout.keycodes[idx]];
-            Keycode::ModifierKeySequence(RIGHT_CTRL_MODIFIER, keycodes)
+pub(crate) fn layout_for_key(layout_key: &str) -> Result<&'static Layout, Error> {
+    LAYOUT_MAP
+        .get(layout_key)
+        .or_else(|| registry::registered_layout(layout_key))
+        .ok_or_else(|| Error::InvalidLayoutKey(layout_key.to_string()))
+}
+
+// NFC so that decomposed input (e.g. 'n' + U+0303) matches the precomposed layout tables. A
+// character composed from several keeps the byte offset of its base character. Starters usually
+// begin a new character but some compose with the one before, e.g. Hangul jamo.
+pub(crate) fn nfc_char_indices(string: &str) -> Vec<(usize, char)> {
+    let mut char_indices = Vec::with_capacity(string.len());
+    let mut push_segment = |start: usize, segment: &str| {
+        if is_nfc(segment) {
+            char_indices.extend(segment.char_indices().map(|(i, c)| (start + i, c)));
+        } else {
+            char_indices.extend(segment.nfc().map(|c| (start, c)));
         }
-        u if u >= UNICODE_FIRST_ASCII && u <= UNICODE_LAST_ASCII => {
-            let idx = (u - UNICODE_FIRST_ASCII) as usize;
-            Keycode::RegularKey(layout.keycodes[idx])
+    };
+
+    let mut segment_start = 0;
+    for (index, c) in string.char_indices() {
+        if index > segment_start
+            && canonical_combining_class(c) == 0
+            && !composes_with(&string[segment_start..index], c)
+        {
+            push_segment(segment_start, &string[segment_start..index]);
+            segment_start = index;
         }
-        _ => Keycode::InvalidCharacter,
     }
+    push_segment(segment_start, &string[segment_start..]);
+
+    char_indices
 }
 
-// https://github.com/PaulStoffregen/cores/blob/master/teensy3/usb_keyboard.c
-fn deadkey_for_keycode(layout: &Layout, keycode: u16) -> Option<u16> {
-    layout.dead_keys_mask.and_then(|dkm| {
-        let keycode = keycode & dkm;
-        if let Some(acute_accent_bits) = layout.deadkeys.acute_accent_bits {
-            if keycode == acute_accent_bits {
-                return layout.deadkeys.deadkey_accute_accent;
+// Whether the starter composes with the end of the segment, e.g. a Hangul vowel with a consonant
+fn composes_with(segment: &str, starter: char) -> bool {
+    segment.nfc().chain(std::iter::once(starter)).nfc().count() <= segment.nfc().count()
+}
+
+// Type a single character using the layout, composing it from a deadkey if needed, otherwise
+// using the fallback
+pub(crate) fn push_char(
+    layout: &Layout,
+    c: char,
+    fallback: Fallback,
+    keys_and_modifiers: &mut Vec<KeyMod>,
+) -> Result<(), Error> {
+    match keycode_for_unicode(layout, c as u32) {
+        Keycode::ModifierKeySequence(modifier, sequence) => {
+            for keycode in sequence {
+                keys_and_modifiers.push(KeyMod {
+                    key: keycode as u8,
+                    modifier: modifier as u8,
+                    release: Release::Keys,
+                    fallback: None,
+                });
             }
+            // Manually add release after sequence is finished
+            keys_and_modifiers.push(KeyMod {
+                key: 0,
+                modifier: 0,
+                release: Release::None,
+                fallback: None,
+            });
         }
-        if let Some(cedilla_bits) = layout.deadkeys.cedilla_bits {
-            if keycode == cedilla_bits {
-                return layout.deadkeys.deadkey_cedilla;
-            }
+        Keycode::RegularKey(keycode) => {
+            push_regular_key(layout, keycode, keys_and_modifiers);
         }
-        if let Some(diaeresis_bits) = layout.deadkeys.diaeresis_bits {
-            if keycode == diaeresis_bits {
-                return layout.deadkeys.deadkey_diaeresis;
+        Keycode::InvalidCharacter => {
+            if !push_decomposed_key(layout, c, keys_and_modifiers) {
+                keys_and_modifiers.extend(fallback.keys_and_modifiers(layout, c)?);
             }
         }
-        if let Some(grave_accent_bits) = layout.deadkeys.grave_accent_bits {
-            if keycode == grave_accent_bits {
-                return layout.deadkeys.deadkey_grave_accent;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn push_regular_key(
+    layout: &Layout,
+    keycode: u16,
+    keys_and_modifiers: &mut Vec<KeyMod>,
+) {
+    if let Some(dead_keycode) = deadkey_for_keycode(layout, keycode) {
+        push_deadkey(layout, dead_keycode, keys_and_modifiers);
+    }
+    let key = key_for_keycode(layout, keycode);
+    let modifier = modifier_for_keycode(layout, keycode);
+    keys_and_modifiers.push(KeyMod {
+        key,
+        modifier,
+        release: Release::All,
+        fallback: None,
+    });
+}
+
+pub(crate) fn push_named_key(key: Key, keys_and_modifiers: &mut Vec<KeyMod>) {
+    keys_and_modifiers.push(KeyMod {
+        key: key.usage(),
+        modifier: 0,
+        release: Release::All,
+        fallback: None,
+    });
+}
+
+fn push_deadkey(layout: &Layout, dead_keycode: u16, keys_and_modifiers: &mut Vec<KeyMod>) {
+    let key = key_for_keycode(layout, dead_keycode);
+    let modifier = modifier_for_keycode(layout, dead_keycode);
+    keys_and_modifiers.push(KeyMod {
+        key,
+        modifier,
+        release: Release::All,
+        fallback: None,
+    });
+}
+
+// A character the layout does not list may still be composed from the deadkey for its accent and
+// its base character, e.g. 'ǹ' -> grave accent deadkey + 'n'
+fn push_decomposed_key(layout: &Layout, c: char, keys_and_modifiers: &mut Vec<KeyMod>) -> bool {
+    let mut decomposed = std::iter::once(c).nfd();
+    let (base, combining) = match (decomposed.next(), decomposed.next(), decomposed.next()) {
+        (Some(base), Some(combining), None) => (base, combining),
+        _ => return false,
+    };
+
+    // RingAbove takes precedence over DegreeSign, which shares its combining character
+    let deadkey = match layout
+        .deadkeys
+        .iter()
+        .filter(|deadkey| deadkey.accent.combining_char() == combining)
+        .min_by_key(|deadkey| deadkey.accent == Accent::DegreeSign)
+    {
+        Some(deadkey) => deadkey,
+        None => return false,
+    };
+
+    match keycode_for_unicode(layout, base as u32) {
+        // The base character must not need a deadkey of its own
+        Keycode::RegularKey(keycode) if deadkey_for_keycode(layout, keycode).is_none() => {
+            push_deadkey(layout, deadkey.keycode, keys_and_modifiers);
+            push_regular_key(layout, keycode, keys_and_modifiers);
+            true
+        }
+        _ => false,
+    }
+}
+
+// Every layout has a key for the letters, digits and space so this cannot fail for them
+pub(crate) fn ascii_keycode(layout: &Layout, c: char) -> u16 {
+    layout.keycodes[(c as u32 - UNICODE_FIRST_ASCII) as usize]
+}
+
+pub(crate) fn keycode_for_unicode(layout: &Layout, unicode: u32) -> Keycode {
+    match unicode {
+        u if u == UNICODE_ENTER => Keycode::RegularKey(ENTER_KEYCODE & layout.keycode_mask),
+        u if u == UNICODE_TAB => Keycode::RegularKey(TAB_KEYCODE & layout.keycode_mask),
+        // Layouts without a key for a character, e.g. '~' on Italian, define it as 0
+        u if u < UNICODE_FIRST_ASCII => {
+            let idx = ((u + CONTROL_CHARACTER_OFFSET) - UNICODE_FIRST_ASCII) as usize;
+            match layout.keycodes[idx] {
+                0 => Keycode::InvalidCharacter,
+                keycode => Keycode::ModifierKeySequence(RIGHT_CTRL_MODIFIER, vec![keycode]),
             }
         }
-        if let Some(circumflex_bits) = layout.deadkeys.circumflex_bits {
-            if keycode == circumflex_bits {
-                return layout.deadkeys.deadkey_circumflex;
+        u if (UNICODE_FIRST_ASCII..=UNICODE_LAST_ASCII).contains(&u) => {
+            let idx = (u - UNICODE_FIRST_ASCII) as usize;
+            match layout.keycodes[idx] {
+                0 => Keycode::InvalidCharacter,
+                keycode => Keycode::RegularKey(keycode),
             }
         }
-        if let Some(tilde_bits) = layout.deadkeys.tilde_bits {
-            if keycode == tilde_bits {
-                return layout.deadkeys.deadkey_tilde;
+        // Unmapped ISO-8859-1 characters are defined as 0 in the layout header
+        u if (UNICODE_FIRST_LATIN1..=UNICODE_LAST_LATIN1).contains(&u) => {
+            let idx = (u - UNICODE_FIRST_LATIN1) as usize;
+            match layout.latin1_keycodes.get(idx) {
+                Some(&keycode) if keycode != 0 => Keycode::RegularKey(keycode),
+                _ => Keycode::InvalidCharacter,
             }
         }
-        None
+        u => layout
+            .extra_keycodes
+            .iter()
+            .find(|(extra_unicode, _)| u32::from(*extra_unicode) == u)
+            .map_or(Keycode::InvalidCharacter, |(_, keycode)| {
+                Keycode::RegularKey(*keycode)
+            }),
+    }
+}
+
+// https://github.com/PaulStoffregen/cores/blob/master/teensy3/usb_keyboard.c
+pub(crate) fn deadkey_for_keycode(layout: &Layout, keycode: u16) -> Option<u16> {
+    layout.dead_keys_mask.and_then(|dkm| {
+        let keycode = keycode & dkm;
+        layout
+            .deadkeys
+            .iter()
+            .find(|deadkey| deadkey.bits == keycode)
+            .map(|deadkey| deadkey.keycode)
     })
 }
 
 // https://github.com/PaulStoffregen/cores/blob/master/usb_hid/usb_api.cpp#L196
-fn modifier_for_keycode(layout: &Layout, keycode: u16) -> u8 {
+pub(crate) fn modifier_for_keycode(layout: &Layout, keycode: u16) -> u8 {
     let mut modifier = 0u16;
 
     if keycode & layout.shift_mask > 0 {
@@ -223,7 +591,7 @@ fn modifier_for_keycode(layout: &Layout, keycode: u16) -> u8 {
 }
 
 // https://github.com/PaulStoffregen/cores/blob/master/usb_hid/usb_api.cpp#L212
-fn key_for_keycode(layout: &Layout, keycode: u16) -> u8 {
+pub(crate) fn key_for_keycode(layout: &Layout, keycode: u16) -> u8 {
     let key = keycode & KEY_MASK;
     match layout.non_us {
         Some(non_us) => {
@@ -0,0 +1,78 @@
+use crate::{Error, Fallback, KeyMod};
+
+use std::fmt;
+use std::str::FromStr;
+
+const REPLACE_PREFIX: &str = "replace:";
+
+/// What to do with a character that can be typed neither by the layout nor by the fallback
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Untypeable {
+    /// Fail with `Error::InvalidCharacter`
+    #[default]
+    Error,
+    /// Leave the character out
+    Skip,
+    /// Type the given character instead
+    Replace(char),
+    /// Type a plain ASCII approximation instead, e.g. '“' -> '"', '—' -> '-', '…' -> "..."
+    Transliterate,
+}
+
+/// Options controlling how a string is turned into key presses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EncodeOptions {
+    pub fallback: Fallback,
+    pub untypeable: Untypeable,
+}
+
+/// A character of the input that was left out or typed as something else
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alteration {
+    /// Byte offset of the character in the input string
+    pub index: usize,
+    pub original: char,
+    /// What was typed instead, `None` when the character was skipped
+    pub replacement: Option<String>,
+}
+
+/// The key presses for a string along with any characters that had to be altered to type it
+#[derive(Debug)]
+pub struct Encoding {
+    pub keys_and_modifiers: Vec<KeyMod>,
+    pub alterations: Vec<Alteration>,
+}
+
+impl fmt::Display for Untypeable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Untypeable::Error => write!(f, "error"),
+            Untypeable::Skip => write!(f, "skip"),
+            Untypeable::Replace(c) => write!(f, "{}{}", REPLACE_PREFIX, c),
+            Untypeable::Transliterate => write!(f, "transliterate"),
+        }
+    }
+}
+
+impl FromStr for Untypeable {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Untypeable, Error> {
+        match s {
+            "error" => Ok(Untypeable::Error),
+            "skip" => Ok(Untypeable::Skip),
+            "transliterate" => Ok(Untypeable::Transliterate),
+            _ => {
+                let mut replacement = s.trim_start_matches(REPLACE_PREFIX).chars();
+                match (
+                    s.starts_with(REPLACE_PREFIX),
+                    replacement.next(),
+                    replacement.next(),
+                ) {
+                    (true, Some(c), None) => Ok(Untypeable::Replace(c)),
+                    _ => Err(Error::InvalidUntypeable(s.to_string())),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use gen_layouts_sys::Layout;
+
+use crate::{
+    key_for_keycode, keys_and_modifiers_to_hid_packets, modifier_for_keycode, KeyMod, Release,
+    HID_PACKET_LEN, RELEASE_KEYS_HID_PACKET,
+};
+
+// The number of key slots in a boot report
+const KEY_SLOTS: usize = 6;
+
+// Consecutive characters on distinct keys with the same modifiers are pressed one more per packet
+// while the earlier ones stay held, then released together: "abc" becomes [a], [a b], [a b c],
+// release. The host sees exactly one new key in each packet so it types them in order.
+pub(crate) fn packed_hid_packets(layout: &Layout, keys_and_modifiers: &[KeyMod]) -> Bytes {
+    let mut packet_bytes = BytesMut::with_capacity(HID_PACKET_LEN * keys_and_modifiers.len() * 2);
+    let mut idx = 0;
+
+    while idx < keys_and_modifiers.len() {
+        let run =
+            &keys_and_modifiers[idx..idx + packable_run_len(layout, &keys_and_modifiers[idx..])];
+        if run.len() < 2 {
+            packet_bytes.put_slice(&keys_and_modifiers_to_hid_packets(
+                &keys_and_modifiers[idx..=idx],
+            ));
+            idx += 1;
+            continue;
+        }
+
+        for pressed in 1..=run.len() {
+            packet_bytes.put_u8(run[0].modifier);
+            packet_bytes.put_u8(0u8);
+            for key_mod in &run[..pressed] {
+                packet_bytes.put_u8(key_mod.key);
+            }
+            packet_bytes.put_slice(&[0u8; KEY_SLOTS][pressed..]);
+        }
+        packet_bytes.put_slice(&RELEASE_KEYS_HID_PACKET);
+        idx += run.len();
+    }
+
+    packet_bytes.freeze()
+}
+
+// The number of key presses at the start that can share packets: at most one per key slot, all
+// with the same modifiers and none pressing the same key twice
+fn packable_run_len(layout: &Layout, keys_and_modifiers: &[KeyMod]) -> usize {
+    let modifier = match keys_and_modifiers.first() {
+        Some(first) => first.modifier,
+        None => return 0,
+    };
+
+    let mut keys = Vec::with_capacity(KEY_SLOTS);
+    for key_mod in keys_and_modifiers.iter().take(KEY_SLOTS) {
+        if !is_packable(layout, key_mod)
+            || key_mod.modifier != modifier
+            || keys.contains(&key_mod.key)
+        {
+            break;
+        }
+        keys.push(key_mod.key);
+    }
+
+    keys.len()
+}
+
+// A plain key press that is released straight away. Deadkeys are left alone as the host composes
+// them with whichever key is pressed next, and so are fallbacks that hold keys between presses.
+fn is_packable(layout: &Layout, key_mod: &KeyMod) -> bool {
+    match key_mod.release {
+        Release::All => {}
+        Release::Keys | Release::None => return false,
+    }
+
+    key_mod.key != 0 && key_mod.fallback.is_none() && !is_deadkey(layout, key_mod)
+}
+
+fn is_deadkey(layout: &Layout, key_mod: &KeyMod) -> bool {
+    layout.deadkeys.iter().any(|deadkey| {
+        key_for_keycode(layout, deadkey.keycode) == key_mod.key
+            && modifier_for_keycode(layout, deadkey.keycode) == key_mod.modifier
+    })
+}
@@ -0,0 +1,38 @@
+use gen_layouts_sys::{Layout, LAYOUT_MAP};
+use lazy_static::lazy_static;
+
+use crate::Error;
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+lazy_static! {
+    // Registered layouts are leaked so they live as long as the built-in ones in LAYOUT_MAP
+    static ref REGISTRY: RwLock<HashMap<&'static str, &'static Layout>> =
+        RwLock::new(HashMap::new());
+}
+
+/// Make the layout available under the given key to everything that takes a layout key, e.g.
+/// `string_to_hid_packets`, scripts and `available_layouts`. The layout is kept for the rest of
+/// the program. Fails if a built-in or registered layout already has the key.
+pub fn register_layout(layout_key: &str, layout: Layout) -> Result<&'static Layout, Error> {
+    let mut registry = REGISTRY.write().unwrap_or_else(|e| e.into_inner());
+    if LAYOUT_MAP.contains_key(layout_key) || registry.contains_key(layout_key) {
+        return Err(Error::DuplicateLayout(layout_key.to_string()));
+    }
+
+    let layout_key: &'static str = Box::leak(layout_key.to_string().into_boxed_str());
+    let layout: &'static Layout = Box::leak(Box::new(layout));
+    registry.insert(layout_key, layout);
+    Ok(layout)
+}
+
+pub(crate) fn registered_layout(layout_key: &str) -> Option<&'static Layout> {
+    let registry = REGISTRY.read().unwrap_or_else(|e| e.into_inner());
+    registry.get(layout_key).copied()
+}
+
+pub(crate) fn registered_layout_keys() -> Vec<&'static str> {
+    let registry = REGISTRY.read().unwrap_or_else(|e| e.into_inner());
+    registry.keys().copied().collect()
+}
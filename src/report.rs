@@ -0,0 +1,239 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use gen_layouts_sys::{MediaKey, SystemKey};
+
+use crate::{Error, KeyRollover, ReportFormat};
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Report ID of the keyboard reports sent by a composite device
+pub const KEYBOARD_REPORT_ID: u8 = 1;
+/// Report ID of the consumer control (media key) reports sent by a composite device
+pub const CONSUMER_REPORT_ID: u8 = 2;
+/// Report ID of the system control (power, sleep and wake up) reports sent by a composite device
+pub const SYSTEM_REPORT_ID: u8 = 3;
+
+/// Report descriptor of a boot keyboard, whose reports are the plain 8 byte HID packets
+pub const KEYBOARD_REPORT_DESCRIPTOR: [u8; 63] = [
+    0x05, 0x01, // Usage Page (Generic Desktop)
+    0x09, 0x06, // Usage (Keyboard)
+    0xA1, 0x01, // Collection (Application)
+    0x05, 0x07, //   Usage Page (Keyboard)
+    0x19, 0xE0, //   Usage Minimum (Left Control)
+    0x29, 0xE7, //   Usage Maximum (Right GUI)
+    0x15, 0x00, //   Logical Minimum (0)
+    0x25, 0x01, //   Logical Maximum (1)
+    0x75, 0x01, //   Report Size (1)
+    0x95, 0x08, //   Report Count (8)
+    0x81, 0x02, //   Input (Data, Variable, Absolute) - Modifiers
+    0x95, 0x01, //   Report Count (1)
+    0x75, 0x08, //   Report Size (8)
+    0x81, 0x03, //   Input (Constant) - Reserved
+    0x95, 0x05, //   Report Count (5)
+    0x75, 0x01, //   Report Size (1)
+    0x05, 0x08, //   Usage Page (LEDs)
+    0x19, 0x01, //   Usage Minimum (Num Lock)
+    0x29, 0x05, //   Usage Maximum (Kana)
+    0x91, 0x02, //   Output (Data, Variable, Absolute) - LEDs
+    0x95, 0x01, //   Report Count (1)
+    0x75, 0x03, //   Report Size (3)
+    0x91, 0x03, //   Output (Constant) - LED padding
+    0x95, 0x06, //   Report Count (6)
+    0x75, 0x08, //   Report Size (8)
+    0x15, 0x00, //   Logical Minimum (0)
+    0x25, 0x65, //   Logical Maximum (101)
+    0x05, 0x07, //   Usage Page (Keyboard)
+    0x19, 0x00, //   Usage Minimum (0)
+    0x29, 0x65, //   Usage Maximum (101)
+    0x81, 0x00, //   Input (Data, Array) - Keys
+    0xC0, // End Collection
+];
+
+/// Report descriptor fragment of the system control reports, as found at the end of
+/// `COMPOSITE_REPORT_DESCRIPTOR`
+#[rustfmt::skip]
+pub const SYSTEM_CONTROL_REPORT_DESCRIPTOR: [u8; 24] = [
+    0x05, 0x01, // Usage Page (Generic Desktop)
+    0x09, 0x80, // Usage (System Control)
+    0xA1, 0x01, // Collection (Application)
+    0x85, SYSTEM_REPORT_ID, //   Report ID
+    0x15, 0x00, //   Logical Minimum (0)
+    0x26, 0xFF, 0x00, //   Logical Maximum (255)
+    0x19, 0x00, //   Usage Minimum (0)
+    0x29, 0xFF, //   Usage Maximum (255)
+    0x75, 0x08, //   Report Size (8)
+    0x95, 0x01, //   Report Count (1)
+    0x81, 0x00, //   Input (Data, Array) - Usage
+    0xC0, // End Collection
+];
+
+/// Report descriptor of a keyboard, consumer control and system control device sharing one
+/// interface. Every report starts with its report ID.
+#[rustfmt::skip]
+pub const COMPOSITE_REPORT_DESCRIPTOR: [u8; 114] = [
+    0x05, 0x01, // Usage Page (Generic Desktop)
+    0x09, 0x06, // Usage (Keyboard)
+    0xA1, 0x01, // Collection (Application)
+    0x85, KEYBOARD_REPORT_ID, //   Report ID
+    0x05, 0x07, //   Usage Page (Keyboard)
+    0x19, 0xE0, //   Usage Minimum (Left Control)
+    0x29, 0xE7, //   Usage Maximum (Right GUI)
+    0x15, 0x00, //   Logical Minimum (0)
+    0x25, 0x01, //   Logical Maximum (1)
+    0x75, 0x01, //   Report Size (1)
+    0x95, 0x08, //   Report Count (8)
+    0x81, 0x02, //   Input (Data, Variable, Absolute) - Modifiers
+    0x95, 0x01, //   Report Count (1)
+    0x75, 0x08, //   Report Size (8)
+    0x81, 0x03, //   Input (Constant) - Reserved
+    0x95, 0x05, //   Report Count (5)
+    0x75, 0x01, //   Report Size (1)
+    0x05, 0x08, //   Usage Page (LEDs)
+    0x19, 0x01, //   Usage Minimum (Num Lock)
+    0x29, 0x05, //   Usage Maximum (Kana)
+    0x91, 0x02, //   Output (Data, Variable, Absolute) - LEDs
+    0x95, 0x01, //   Report Count (1)
+    0x75, 0x03, //   Report Size (3)
+    0x91, 0x03, //   Output (Constant) - LED padding
+    0x95, 0x06, //   Report Count (6)
+    0x75, 0x08, //   Report Size (8)
+    0x15, 0x00, //   Logical Minimum (0)
+    0x25, 0x65, //   Logical Maximum (101)
+    0x05, 0x07, //   Usage Page (Keyboard)
+    0x19, 0x00, //   Usage Minimum (0)
+    0x29, 0x65, //   Usage Maximum (101)
+    0x81, 0x00, //   Input (Data, Array) - Keys
+    0xC0, // End Collection
+    0x05, 0x0C, // Usage Page (Consumer)
+    0x09, 0x01, // Usage (Consumer Control)
+    0xA1, 0x01, // Collection (Application)
+    0x85, CONSUMER_REPORT_ID, //   Report ID
+    0x15, 0x00, //   Logical Minimum (0)
+    0x26, 0xFF, 0x03, //   Logical Maximum (1023)
+    0x19, 0x00, //   Usage Minimum (0)
+    0x2A, 0xFF, 0x03, //   Usage Maximum (1023)
+    0x75, 0x10, //   Report Size (16)
+    0x95, 0x01, //   Report Count (1)
+    0x81, 0x00, //   Input (Data, Array) - Usage
+    0xC0, // End Collection
+    0x05, 0x01, // Usage Page (Generic Desktop)
+    0x09, 0x80, // Usage (System Control)
+    0xA1, 0x01, // Collection (Application)
+    0x85, SYSTEM_REPORT_ID, //   Report ID
+    0x15, 0x00, //   Logical Minimum (0)
+    0x26, 0xFF, 0x00, //   Logical Maximum (255)
+    0x19, 0x00, //   Usage Minimum (0)
+    0x29, 0xFF, //   Usage Maximum (255)
+    0x75, 0x08, //   Report Size (8)
+    0x95, 0x01, //   Report Count (1)
+    0x81, 0x00, //   Input (Data, Array) - Usage
+    0xC0, // End Collection
+];
+
+/// How the HID device the reports are written to is set up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Device {
+    /// A boot keyboard, see `KEYBOARD_REPORT_DESCRIPTOR`. Media and system keys cannot be sent.
+    #[default]
+    Keyboard,
+    /// A keyboard, consumer control and system control device, see `COMPOSITE_REPORT_DESCRIPTOR`
+    Composite,
+    /// A keyboard whose reports are in the given format, e.g. an NKRO bitmap. Media and system
+    /// keys cannot be sent.
+    Custom(ReportFormat),
+}
+
+impl Device {
+    /// The descriptor of the device, `None` for a custom one as it is defined by the device
+    pub fn report_descriptor(self) -> Option<&'static [u8]> {
+        match self {
+            Device::Keyboard => Some(&KEYBOARD_REPORT_DESCRIPTOR),
+            Device::Composite => Some(&COMPOSITE_REPORT_DESCRIPTOR),
+            Device::Custom(_) => None,
+        }
+    }
+
+    /// The format of the device's keyboard reports
+    pub fn keyboard_format(self) -> ReportFormat {
+        match self {
+            Device::Keyboard => ReportFormat::default(),
+            Device::Composite => ReportFormat {
+                rollover: KeyRollover::Boot,
+                report_id: Some(KEYBOARD_REPORT_ID),
+            },
+            Device::Custom(format) => format,
+        }
+    }
+
+    /// Render 8 byte keyboard packets as the reports to write to the device
+    pub fn keyboard_reports(self, packets: &[u8]) -> Result<Vec<Bytes>, Error> {
+        let format = self.keyboard_format();
+        let reports = format.render(packets)?;
+        Ok(reports
+            .chunks(format.report_len())
+            .map(|report| Bytes::from(report.to_vec()))
+            .collect())
+    }
+
+    /// The reports that press and release a media key
+    pub fn media_key_reports(self, media_key: MediaKey) -> Result<Vec<Bytes>, Error> {
+        match self {
+            Device::Keyboard | Device::Custom(_) => {
+                Err(Error::UnsupportedByDevice(media_key.to_string()))
+            }
+            Device::Composite => Ok(vec![
+                with_report_id(CONSUMER_REPORT_ID, &media_key.usage().to_le_bytes()),
+                with_report_id(CONSUMER_REPORT_ID, &[0u8; 2]),
+            ]),
+        }
+    }
+
+    /// The reports that press and release a system key
+    pub fn system_key_reports(self, system_key: SystemKey) -> Result<Vec<Bytes>, Error> {
+        match self {
+            Device::Keyboard | Device::Custom(_) => {
+                Err(Error::UnsupportedByDevice(system_key.to_string()))
+            }
+            Device::Composite => Ok(vec![
+                with_report_id(SYSTEM_REPORT_ID, &[system_key.usage()]),
+                with_report_id(SYSTEM_REPORT_ID, &[0u8]),
+            ]),
+        }
+    }
+}
+
+fn with_report_id(report_id: u8, report: &[u8]) -> Bytes {
+    let mut bytes = BytesMut::with_capacity(report.len() + 1);
+    bytes.put_u8(report_id);
+    bytes.put_slice(report);
+    bytes.freeze()
+}
+
+impl fmt::Display for Device {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Device::Keyboard => write!(f, "keyboard"),
+            Device::Composite => write!(f, "composite"),
+            Device::Custom(ReportFormat {
+                rollover,
+                report_id: None,
+            }) => write!(f, "{}", rollover),
+            Device::Custom(ReportFormat {
+                rollover,
+                report_id: Some(report_id),
+            }) => write!(f, "{} with report ID {}", rollover, report_id),
+        }
+    }
+}
+
+impl FromStr for Device {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Device, Error> {
+        match s {
+            "keyboard" => Ok(Device::Keyboard),
+            "composite" => Ok(Device::Composite),
+            _ => Err(Error::InvalidDevice(s.to_string())),
+        }
+    }
+}
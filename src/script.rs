@@ -0,0 +1,442 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use gen_layouts_sys::*;
+
+use crate::{
+    keys_and_modifiers_to_hid_packets, layout_for_key, Chord, Device, EncodeOptions, Error,
+    LayoutExt, HID_PACKET_LEN,
+};
+
+use std::time::Duration;
+
+const COMMENT_PREFIX: &str = "#";
+const COMMENT_KEYWORD: &str = "REM";
+// The first two bytes of a report are the modifiers and a reserved byte
+const KEYS_OFFSET: usize = 2;
+// One key slot is left for the keys pressed while holding
+const MAX_HELD_KEYS: usize = HID_PACKET_LEN - KEYS_OFFSET - 1;
+// Every repeat is compiled into reports up front, so the count is kept to what a script needs
+const MAX_REPEAT_COUNT: u64 = 10_000;
+
+/// A step of a compiled script: reports to write to the device one at a time or a pause before
+/// the next step
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Step {
+    Reports(Vec<Bytes>),
+    Delay(Duration),
+}
+
+/// A parsed keystroke script. One command per line, keywords are case insensitive:
+///
+/// ```text
+/// # Comments start with '#' or REM
+/// LAYOUT LAYOUT_GERMAN
+/// TYPE Grüße aus Berlin
+/// PRESS ENTER
+/// PRESS CTRL+ALT+T
+/// MEDIA VOLUME_INC
+/// SYSTEM SLEEP
+/// DEFAULT_DELAY 20
+/// DELAY 500
+/// REPEAT 3
+/// HOLD ALT
+/// PRESS TAB TAB
+/// RELEASE
+/// ```
+///
+/// `TYPE` types the rest of the line as it is. `PRESS` takes one or more chords separated by
+/// spaces. `MEDIA` and `SYSTEM` take one or more media or system keys, which need a
+/// `Device::Composite`. `DEFAULT_DELAY` waits the given milliseconds after every command that
+/// follows. `REPEAT` runs the previous command again the given number of times, up to 10000.
+/// `HOLD` keeps modifiers or keys down until a `RELEASE` of the same, or a `RELEASE` on its own of
+/// everything.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Script {
+    pub(crate) lines: Vec<ScriptLine>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ScriptLine {
+    pub line: usize,
+    /// Column of the command's argument, where any error about it is reported
+    pub column: usize,
+    pub command: Command,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Command {
+    Type(String),
+    Press(Vec<Chord>),
+    Media(Vec<MediaKey>),
+    System(Vec<SystemKey>),
+    Delay(Duration),
+    DefaultDelay(Duration),
+    Repeat(usize),
+    Hold(Vec<Chord>),
+    /// Releases everything when empty
+    Release(Vec<Chord>),
+    Layout(String),
+}
+
+// What HOLD keeps pressed while the rest of the script runs
+#[derive(Default)]
+struct Held {
+    modifier: u8,
+    keys: Vec<u8>,
+}
+
+impl Script {
+    pub fn parse(script: &str) -> Result<Script, Error> {
+        let mut lines = Vec::new();
+
+        for (idx, line) in script.lines().enumerate() {
+            let line_number = idx + 1;
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with(COMMENT_PREFIX) {
+                continue;
+            }
+
+            let keyword_start = line.len() - trimmed.len();
+            let keyword_end = trimmed
+                .find(char::is_whitespace)
+                .map_or(line.len(), |idx| keyword_start + idx);
+            let keyword = line[keyword_start..keyword_end].to_uppercase();
+            if keyword == COMMENT_KEYWORD {
+                continue;
+            }
+
+            // Only the single separator is dropped so TYPE keeps any other whitespace
+            let argument_start = line[keyword_end..]
+                .chars()
+                .next()
+                .map_or(keyword_end, |c| keyword_end + c.len_utf8());
+            let argument = &line[argument_start..];
+            let column = column_of(line, argument_start);
+            let error = |column: usize, message: String| Error::InvalidScript {
+                line: line_number,
+                column,
+                message,
+            };
+
+            let command = match keyword.as_str() {
+                "TYPE" => Command::Type(argument.to_string()),
+                "PRESS" | "HOLD" | "RELEASE" => {
+                    let chords = words(line, argument_start)
+                        .map(|(word_column, word)| {
+                            word.parse::<Chord>()
+                                .map_err(|e| error(word_column, e.to_string()))
+                        })
+                        .collect::<Result<Vec<Chord>, Error>>()?;
+                    match keyword.as_str() {
+                        "PRESS" if chords.is_empty() => {
+                            return Err(error(column, "PRESS needs a key or chord".to_string()))
+                        }
+                        "PRESS" => Command::Press(chords),
+                        "HOLD" if chords.is_empty() => {
+                            return Err(error(column, "HOLD needs a key or modifier".to_string()))
+                        }
+                        "HOLD" => Command::Hold(chords),
+                        _ => Command::Release(chords),
+                    }
+                }
+                "MEDIA" => {
+                    let media_keys = words(line, argument_start)
+                        .map(|(word_column, word)| {
+                            word.parse::<MediaKey>()
+                                .map_err(|e| error(word_column, e.to_string()))
+                        })
+                        .collect::<Result<Vec<MediaKey>, Error>>()?;
+                    if media_keys.is_empty() {
+                        return Err(error(column, "MEDIA needs a media key".to_string()));
+                    }
+                    Command::Media(media_keys)
+                }
+                "SYSTEM" => {
+                    let system_keys = words(line, argument_start)
+                        .map(|(word_column, word)| {
+                            word.parse::<SystemKey>()
+                                .map_err(|e| error(word_column, e.to_string()))
+                        })
+                        .collect::<Result<Vec<SystemKey>, Error>>()?;
+                    if system_keys.is_empty() {
+                        return Err(error(column, "SYSTEM needs a system key".to_string()));
+                    }
+                    Command::System(system_keys)
+                }
+                "DELAY" | "DEFAULT_DELAY" => {
+                    let millis = parse_number(argument).map_err(|e| error(column, e))?;
+                    match keyword.as_str() {
+                        "DELAY" => Command::Delay(Duration::from_millis(millis)),
+                        _ => Command::DefaultDelay(Duration::from_millis(millis)),
+                    }
+                }
+                "REPEAT" => {
+                    let count = parse_repeat_count(argument).map_err(|e| error(column, e))?;
+                    if !lines.iter().any(is_repeatable) {
+                        return Err(error(
+                            column,
+                            "REPEAT needs a command before it".to_string(),
+                        ));
+                    }
+                    Command::Repeat(count)
+                }
+                "LAYOUT" => {
+                    let layout_key = argument.trim();
+                    if let Err(e) = layout_for_key(layout_key) {
+                        return Err(error(column, e.to_string()));
+                    }
+                    Command::Layout(layout_key.to_string())
+                }
+                _ => {
+                    return Err(error(
+                        column_of(line, keyword_start),
+                        format!("Unknown command: {}", &line[keyword_start..keyword_end]),
+                    ))
+                }
+            };
+
+            lines.push(ScriptLine {
+                line: line_number,
+                column,
+                command,
+            });
+        }
+
+        Ok(Script { lines })
+    }
+
+    /// Turn the script into boot keyboard reports and delays, starting with the given layout. Any
+    /// character the layout cannot type is handled according to the options.
+    pub fn compile(&self, layout_key: &str, options: EncodeOptions) -> Result<Vec<Step>, Error> {
+        self.compile_for_device(layout_key, options, Device::Keyboard)
+    }
+
+    /// Like `compile` but creates the reports for the given device
+    pub fn compile_for_device(
+        &self,
+        layout_key: &str,
+        options: EncodeOptions,
+        device: Device,
+    ) -> Result<Vec<Step>, Error> {
+        let mut layout = layout_for_key(layout_key)?;
+        let mut held = Held::default();
+        let mut steps = Vec::new();
+        let mut previous: Option<&ScriptLine> = None;
+        let mut default_delay = Duration::default();
+
+        for script_line in self.lines.iter() {
+            let (script_line, count) = match script_line.command {
+                Command::DefaultDelay(delay) => {
+                    default_delay = delay;
+                    continue;
+                }
+                Command::Repeat(count) => (previous.expect("Checked when parsing"), count),
+                _ => {
+                    previous = Some(script_line);
+                    (script_line, 1)
+                }
+            };
+
+            for _ in 0..count {
+                run_command(
+                    script_line,
+                    &mut layout,
+                    &mut held,
+                    options,
+                    device,
+                    &mut steps,
+                )
+                .map_err(|e| script_line.error(e))?;
+                if default_delay > Duration::default() {
+                    steps.push(Step::Delay(default_delay));
+                }
+            }
+        }
+
+        if !held.keys.is_empty() || held.modifier != 0 {
+            push_reports(&mut steps, device.keyboard_reports(&[0u8; HID_PACKET_LEN])?);
+        }
+
+        Ok(steps)
+    }
+}
+
+fn run_command(
+    script_line: &ScriptLine,
+    layout: &mut &'static Layout,
+    held: &mut Held,
+    options: EncodeOptions,
+    device: Device,
+    steps: &mut Vec<Step>,
+) -> Result<(), Error> {
+    match script_line.command {
+        Command::Type(ref text) => {
+            let keys_and_modifiers = layout.keys_and_modifiers(text, options)?.keys_and_modifiers;
+            let packets = keys_and_modifiers_to_hid_packets(&keys_and_modifiers);
+            push_reports(steps, device.keyboard_reports(&held.apply(&packets))?);
+        }
+        Command::Press(ref chords) => {
+            for chord in chords {
+                let packets =
+                    keys_and_modifiers_to_hid_packets(&layout.chord_keys_and_modifiers(chord)?);
+                push_reports(steps, device.keyboard_reports(&held.apply(&packets))?);
+            }
+        }
+        Command::Media(ref media_keys) => {
+            for media_key in media_keys {
+                push_reports(steps, device.media_key_reports(*media_key)?);
+            }
+        }
+        Command::System(ref system_keys) => {
+            for system_key in system_keys {
+                push_reports(steps, device.system_key_reports(*system_key)?);
+            }
+        }
+        Command::Delay(duration) => steps.push(Step::Delay(duration)),
+        Command::DefaultDelay(_) | Command::Repeat(_) => {}
+        Command::Hold(ref chords) => {
+            for chord in chords {
+                let (modifier, key) = chord_state(layout, chord)?;
+                held.modifier |= modifier;
+                if key != 0 && !held.keys.contains(&key) {
+                    if held.keys.len() == MAX_HELD_KEYS {
+                        return Err(Error::InvalidChord(format!(
+                            "{} (no more than {} keys can be held)",
+                            chord, MAX_HELD_KEYS
+                        )));
+                    }
+                    held.keys.push(key);
+                }
+            }
+            push_reports(
+                steps,
+                device.keyboard_reports(&held.apply(&[0u8; HID_PACKET_LEN]))?,
+            );
+        }
+        Command::Release(ref chords) => {
+            if chords.is_empty() {
+                *held = Held::default();
+            }
+            for chord in chords {
+                let (modifier, key) = chord_state(layout, chord)?;
+                held.modifier &= !modifier;
+                held.keys.retain(|held_key| *held_key != key);
+            }
+            push_reports(
+                steps,
+                device.keyboard_reports(&held.apply(&[0u8; HID_PACKET_LEN]))?,
+            );
+        }
+        Command::Layout(ref layout_key) => *layout = layout_for_key(layout_key)?,
+    }
+
+    Ok(())
+}
+
+impl ScriptLine {
+    // Points at the character a TYPE command could not type rather than the start of its text
+    fn error(&self, error: Error) -> Error {
+        let column = match (&self.command, &error) {
+            (Command::Type(text), Error::InvalidCharacter(c)) => text
+                .find(*c)
+                .map_or(self.column, |idx| self.column + text[..idx].chars().count()),
+            _ => self.column,
+        };
+
+        Error::InvalidScript {
+            line: self.line,
+            column,
+            message: error.to_string(),
+        }
+    }
+}
+
+impl Held {
+    // Adds the held modifiers and keys to every packet
+    fn apply(&self, packets: &[u8]) -> Bytes {
+        let mut held_packets = BytesMut::with_capacity(packets.len());
+        for packet in packets.chunks(HID_PACKET_LEN) {
+            let key = packet[KEYS_OFFSET];
+            held_packets.put_u8(packet[0] | self.modifier);
+            held_packets.put_u8(0u8);
+            // Reports list the pressed keys first, so a release packet's empty slot goes last
+            let mut n_keys = 0;
+            if key != 0 {
+                held_packets.put_u8(key);
+                n_keys += 1;
+            }
+            for held_key in self.keys.iter().filter(|held_key| **held_key != key) {
+                held_packets.put_u8(*held_key);
+                n_keys += 1;
+            }
+            for _ in n_keys..HID_PACKET_LEN - KEYS_OFFSET {
+                held_packets.put_u8(0u8);
+            }
+        }
+        held_packets.freeze()
+    }
+}
+
+// The modifier bits and key a chord presses
+fn chord_state(layout: &Layout, chord: &Chord) -> Result<(u8, u8), Error> {
+    let pressed = layout.chord_keys_and_modifiers(chord)?;
+    let last = pressed.last().expect("A chord presses at least one key");
+    Ok((last.modifier, last.key))
+}
+
+// Consecutive reports are written without a pause so they are kept together
+fn push_reports(steps: &mut Vec<Step>, reports: Vec<Bytes>) {
+    if let Some(Step::Reports(previous)) = steps.last_mut() {
+        previous.extend(reports);
+    } else {
+        steps.push(Step::Reports(reports));
+    }
+}
+
+pub(crate) fn is_repeatable(script_line: &ScriptLine) -> bool {
+    !matches!(
+        script_line.command,
+        Command::DefaultDelay(_) | Command::Repeat(_)
+    )
+}
+
+pub(crate) fn parse_number(argument: &str) -> Result<u64, String> {
+    argument
+        .trim()
+        .parse::<u64>()
+        .map_err(|_| format!("Expected a number but found '{}'", argument.trim()))
+}
+
+pub(crate) fn parse_repeat_count(argument: &str) -> Result<usize, String> {
+    match parse_number(argument)? {
+        count if count > MAX_REPEAT_COUNT => Err(format!(
+            "Expected at most {} repeats but found {}",
+            MAX_REPEAT_COUNT, count
+        )),
+        count => Ok(count as usize),
+    }
+}
+
+// Whitespace separated words of the line from the given byte offset, with their columns
+fn words(line: &str, start: usize) -> impl Iterator<Item = (usize, &str)> {
+    let mut words = Vec::new();
+    let mut word_start = None;
+    for (idx, c) in line[start..].char_indices() {
+        match (c.is_whitespace(), word_start) {
+            (false, None) => word_start = Some(start + idx),
+            (true, Some(word_idx)) => {
+                words.push((column_of(line, word_idx), &line[word_idx..start + idx]));
+                word_start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(word_idx) = word_start {
+        words.push((column_of(line, word_idx), &line[word_idx..]));
+    }
+    words.into_iter()
+}
+
+// 1-based column in characters of a byte offset
+pub(crate) fn column_of(line: &str, byte_offset: usize) -> usize {
+    line[..byte_offset].chars().count() + 1
+}
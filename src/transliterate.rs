@@ -0,0 +1,57 @@
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// A plain ASCII approximation of the given character, if one is known
+pub(crate) fn transliterate(c: char) -> Option<String> {
+    let ascii = match c {
+        '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{2032}' => "'",
+        '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' | '\u{2033}' => "\"",
+        '\u{2010}' | '\u{2011}' | '\u{2012}' | '\u{2013}' | '\u{2014}' | '\u{2015}'
+        | '\u{2212}' => "-",
+        '\u{2026}' => "...",
+        '\u{00A0}' | '\u{2000}'..='\u{200A}' | '\u{202F}' | '\u{205F}' | '\u{3000}' => " ",
+        // Invisible characters are dropped
+        '\u{00AD}' | '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}' => "",
+        '\u{2022}' | '\u{00B7}' => "*",
+        '\u{2039}' => "<",
+        '\u{203A}' => ">",
+        '\u{00AB}' => "<<",
+        '\u{00BB}' => ">>",
+        '\u{00D7}' => "x",
+        '\u{00F7}' => "/",
+        '\u{00A9}' => "(C)",
+        '\u{00AE}' => "(R)",
+        '\u{2122}' => "(TM)",
+        '\u{20AC}' => "EUR",
+        '\u{00BC}' => "1/4",
+        '\u{00BD}' => "1/2",
+        '\u{00BE}' => "3/4",
+        '\u{00DF}' => "ss",
+        '\u{00C6}' => "AE",
+        '\u{00E6}' => "ae",
+        '\u{0152}' => "OE",
+        '\u{0153}' => "oe",
+        '\u{00D8}' => "O",
+        '\u{00F8}' => "o",
+        '\u{0141}' => "L",
+        '\u{0142}' => "l",
+        '\u{0110}' | '\u{00D0}' => "D",
+        '\u{0111}' | '\u{00F0}' => "d",
+        '\u{00DE}' => "TH",
+        '\u{00FE}' => "th",
+        _ => return strip_accents(c),
+    };
+
+    Some(ascii.to_string())
+}
+
+// 'é' -> 'e'
+fn strip_accents(c: char) -> Option<String> {
+    let mut decomposed = std::iter::once(c).nfd();
+    match decomposed.next() {
+        Some(base) if base.is_ascii() && base != c && decomposed.all(is_combining_mark) => {
+            Some(base.to_string())
+        }
+        _ => None,
+    }
+}
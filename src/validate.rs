@@ -0,0 +1,59 @@
+use gen_layouts_sys::Layout;
+
+use crate::{KEY_MASK, UNICODE_FIRST_ASCII};
+
+// One keycode per character from SPACE to BACKSPACE
+const KEYCODES_LEN: usize = 96;
+// One keycode per character from NO-BREAK SPACE to LATIN SMALL LETTER Y WITH DIAERESIS
+const LATIN1_KEYCODES_LEN: usize = 96;
+
+// Checks a layout read from a file or imported from another format. Every printable ASCII
+// character is looked up by its offset in the keycode table, and every deadkey by its bits.
+pub(crate) fn validate(layout: Layout) -> Result<Layout, String> {
+    if layout.keycodes.len() != KEYCODES_LEN {
+        return Err(format!(
+            "keycodes needs {} entries, one per character from ' ' to DEL, got {}",
+            KEYCODES_LEN,
+            layout.keycodes.len()
+        ));
+    }
+
+    // Every keyboard types the letters, digits and space. Other characters may be missing, e.g.
+    // '`' and '~' on an Italian keyboard.
+    for (idx, keycode) in layout.keycodes.iter().enumerate() {
+        let c = std::char::from_u32(UNICODE_FIRST_ASCII + idx as u32).unwrap_or_default();
+        let problem = if *keycode == 0 && (c.is_ascii_alphanumeric() || c == ' ') {
+            format!("no key types {:?}", c)
+        } else if *keycode & !layout.keycode_mask != 0 {
+            format!(
+                "keycode {:#X} for {:?} has bits outside keycode_mask {:#X}",
+                keycode, c, layout.keycode_mask
+            )
+        } else if *keycode != 0 && *keycode & KEY_MASK == 0 {
+            format!("keycode {:#X} for {:?} presses no key", keycode, c)
+        } else {
+            continue;
+        };
+        return Err(problem);
+    }
+
+    let dead_keys_mask = layout.dead_keys_mask.unwrap_or(0);
+    for deadkey in layout.deadkeys.iter() {
+        if deadkey.bits == 0 || deadkey.bits & !dead_keys_mask != 0 {
+            return Err(format!(
+                "deadkey {:?} bits {:#X} are outside dead_keys_mask {:#X}",
+                deadkey.accent, deadkey.bits, dead_keys_mask
+            ));
+        }
+    }
+
+    if layout.latin1_keycodes.len() > LATIN1_KEYCODES_LEN {
+        return Err(format!(
+            "latin1_keycodes has at most {} entries, one per character from NO-BREAK SPACE to 'ÿ', got {}",
+            LATIN1_KEYCODES_LEN,
+            layout.latin1_keycodes.len()
+        ));
+    }
+
+    Ok(layout)
+}
@@ -0,0 +1,452 @@
+use gen_layouts_sys::{Accent, Layout};
+
+use crate::import::{LayoutBuilder, Level};
+use crate::keysyms::KEYSYMS;
+use crate::Error;
+
+use std::fs;
+use std::path::Path;
+
+// Keysyms 0x1000000 and up are the Unicode code point plus this offset
+const UNICODE_KEYSYM_OFFSET: u32 = 0x100_0000;
+// Includes deeper than this are taken to be a cycle
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// Load the variant of the layout in an XKB symbols file, e.g. `/usr/share/X11/xkb/symbols/de`
+/// and `"nodeadkeys"`, or the file's default variant if `None`. Files it includes are read from
+/// the same directory. Only the first group and the first four levels are used: none, Shift,
+/// AltGr and Shift+AltGr.
+pub fn load_xkb_layout<P: AsRef<Path>>(path: P, variant: Option<&str>) -> Result<Layout, Error> {
+    let path = path.as_ref();
+    let mut keys = Vec::new();
+    read_symbols(path, variant, Merge::Override, &mut keys, 0)?;
+
+    let mut builder = LayoutBuilder::default();
+    for (name, syms) in keys {
+        let usage = match usage_for_key_name(&name) {
+            Some(usage) => usage,
+            None => continue,
+        };
+        for (idx, sym) in syms.into_iter().enumerate() {
+            match (Level::from_index(idx), sym) {
+                (Some(level), Some(Sym::Char(c))) => builder.add_char(c, usage, level),
+                (Some(level), Some(Sym::Dead(accent))) => builder.add_deadkey(accent, usage, level),
+                _ => {}
+            }
+        }
+    }
+
+    builder
+        .build()
+        .map_err(|message| Error::InvalidLayoutFile(format!("{}: {}", path.display(), message)))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    KeyName(String),
+    Punct(char),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Sym {
+    Char(char),
+    Dead(Accent),
+}
+
+// Whether a key defined again replaces the earlier definition
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Merge {
+    Override,
+    Augment,
+}
+
+// Each key name and what its levels type, in the order the keys were first defined
+type Keys = Vec<(String, Vec<Option<Sym>>)>;
+
+struct Block {
+    name: String,
+    default: bool,
+    body: Vec<(Token, usize)>,
+}
+
+fn read_symbols(
+    path: &Path,
+    variant: Option<&str>,
+    merge: Merge,
+    keys: &mut Keys,
+    depth: usize,
+) -> Result<(), Error> {
+    let invalid_file =
+        |message: String| Error::InvalidLayoutFile(format!("{}: {}", path.display(), message));
+    let invalid =
+        |line: usize, message: String| invalid_file(format!("line {}: {}", line, message));
+    if depth > MAX_INCLUDE_DEPTH {
+        return Err(invalid_file("includes are nested too deeply".to_string()));
+    }
+
+    let contents = fs::read_to_string(path).map_err(|e| invalid_file(e.to_string()))?;
+    let blocks = parse_blocks(tokenize(&contents)).map_err(|(line, m)| invalid(line, m))?;
+    let block = match variant {
+        Some(variant) => blocks.iter().find(|block| block.name == variant),
+        None => blocks
+            .iter()
+            .find(|block| block.default)
+            .or_else(|| blocks.first()),
+    }
+    .ok_or_else(|| invalid_file(format!("no variant {}", variant.unwrap_or("defined"))))?;
+
+    let body = &block.body;
+    let mut idx = 0;
+    while idx < body.len() {
+        let line = body[idx].1;
+        let statement_merge = match &body[idx].0 {
+            Token::Ident(word) if word == "augment" => Some(Merge::Augment),
+            Token::Ident(word) if word == "include" || word == "override" || word == "replace" => {
+                Some(Merge::Override)
+            }
+            _ => None,
+        };
+
+        match (statement_merge, body.get(idx + 1).map(|(token, _)| token)) {
+            (Some(statement_merge), Some(Token::Str(include))) => {
+                let dir = path.parent().unwrap_or_else(|| Path::new("."));
+                let includes = parse_include(include).map_err(|m| invalid(line, m))?;
+                for (file, variant, include_merge) in includes {
+                    let merge = if include_merge == Merge::Augment {
+                        Merge::Augment
+                    } else {
+                        statement_merge
+                    };
+                    read_symbols(&dir.join(file), variant, merge, keys, depth + 1)?;
+                }
+                idx += 2;
+            }
+            (Some(statement_merge), Some(Token::Ident(word))) if word == "key" => {
+                idx =
+                    read_key(body, idx + 1, statement_merge, keys).map_err(|m| invalid(line, m))?;
+            }
+            // Not `key.type = ...`
+            (None, Some(Token::KeyName(_))) if body[idx].0 == Token::Ident("key".to_string()) => {
+                idx = read_key(body, idx, merge, keys).map_err(|m| invalid(line, m))?;
+            }
+            _ => idx = skip_statement(body, idx),
+        }
+        if let Some((Token::Punct(';'), _)) = body.get(idx) {
+            idx += 1;
+        }
+    }
+
+    Ok(())
+}
+
+// `key <NAME> { ... }` starting at `idx`, returning the index after the closing brace
+fn read_key(
+    body: &[(Token, usize)],
+    idx: usize,
+    merge: Merge,
+    keys: &mut Keys,
+) -> Result<usize, String> {
+    let name = match body.get(idx + 1) {
+        Some((Token::KeyName(name), _)) => name.clone(),
+        _ => return Err("expected a key name after key".to_string()),
+    };
+    if body.get(idx + 2).map(|(token, _)| token) != Some(&Token::Punct('{')) {
+        return Err(format!("expected {{ after <{}>", name));
+    }
+    let end = closing(body, idx + 2).ok_or_else(|| format!("unclosed {{ for <{}>", name))?;
+    let syms = first_group(&body[idx + 3..end])?;
+
+    match keys.iter_mut().find(|(existing, _)| *existing == name) {
+        Some((_, existing)) if merge == Merge::Override => *existing = syms,
+        Some(_) => {}
+        None => keys.push((name, syms)),
+    }
+    Ok(end + 1)
+}
+
+// The keysyms of the first group: either `symbols[Group1] = [ ... ]` or the first bare `[ ... ]`
+fn first_group(tokens: &[(Token, usize)]) -> Result<Vec<Option<Sym>>, String> {
+    let token = |idx: usize| tokens.get(idx).map(|(token, _)| token);
+    let punct = |idx: usize, c: char| token(idx) == Some(&Token::Punct(c));
+
+    let mut start = None;
+    for idx in 0..tokens.len() {
+        let is_group1 = match token(idx + 2) {
+            Some(Token::Ident(group)) => group.eq_ignore_ascii_case("group1"),
+            _ => false,
+        };
+        if token(idx) == Some(&Token::Ident("symbols".to_string()))
+            && punct(idx + 1, '[')
+            && is_group1
+            && punct(idx + 3, ']')
+            && punct(idx + 4, '=')
+            && punct(idx + 5, '[')
+        {
+            start = Some(idx + 5);
+            break;
+        }
+        if punct(idx, '[') && (idx == 0 || punct(idx - 1, ',')) && start.is_none() {
+            start = Some(idx);
+        }
+    }
+
+    let start = match start {
+        Some(start) => start,
+        None => return Ok(Vec::new()),
+    };
+    let mut syms = Vec::new();
+    for (token, _) in &tokens[start + 1..] {
+        match token {
+            Token::Ident(name) => syms.push(keysym(name)),
+            Token::Punct(',') => {}
+            Token::Punct(']') => return Ok(syms),
+            token => return Err(format!("unexpected {:?} in the symbols", token)),
+        }
+    }
+    Err("unclosed [ in the symbols".to_string())
+}
+
+// A file, the variant in it and how its keys merge with those already read
+type Include<'a> = (&'a str, Option<&'a str>, Merge);
+
+// "pc+de(nodeadkeys)|latin:1" is several files, those after a | only adding keys not yet defined.
+// Includes for groups other than the first are skipped.
+fn parse_include(include: &str) -> Result<Vec<Include<'_>>, String> {
+    let malformed = || format!("malformed include \"{}\"", include);
+    let mut includes = Vec::new();
+    let mut merge = Merge::Override;
+    let mut rest = include;
+    while let Some(first) = rest.chars().next() {
+        let end = rest[first.len_utf8()..]
+            .find(['+', '|'])
+            .map_or(rest.len(), |end| end + first.len_utf8());
+        let mut part = &rest[..end];
+        if part.starts_with('|') {
+            merge = Merge::Augment;
+            part = &part[1..];
+        } else if part.starts_with('+') {
+            merge = Merge::Override;
+            part = &part[1..];
+        }
+        rest = &rest[end..];
+
+        let (part, group) = match part.find(':') {
+            Some(colon) => (&part[..colon], &part[colon + 1..]),
+            None => (part, "1"),
+        };
+        if group.parse::<u8>().is_err() {
+            return Err(malformed());
+        }
+        let (file, variant) = match (part.find('('), part.strip_suffix(')')) {
+            (Some(open), Some(part)) => (&part[..open], Some(&part[open + 1..])),
+            (None, None) => (part, None),
+            _ => return Err(malformed()),
+        };
+        let invalid_name = |name: &str| name.is_empty() || name.contains(['(', ')']);
+        if invalid_name(file) || variant.is_some_and(invalid_name) {
+            return Err(malformed());
+        }
+        if group == "1" {
+            includes.push((file, variant, merge));
+        }
+    }
+    Ok(includes)
+}
+
+fn parse_blocks(tokens: Vec<(Token, usize)>) -> Result<Vec<Block>, (usize, String)> {
+    let mut blocks = Vec::new();
+    let mut default = false;
+    let mut idx = 0;
+    while idx < tokens.len() {
+        let (token, line) = &tokens[idx];
+        match token {
+            Token::Ident(word) if word == "xkb_symbols" => {
+                let name = match tokens.get(idx + 1) {
+                    Some((Token::Str(name), _)) => name.clone(),
+                    _ => return Err((*line, "expected a name after xkb_symbols".to_string())),
+                };
+                let end = match tokens.get(idx + 2) {
+                    Some((Token::Punct('{'), _)) => closing(&tokens, idx + 2),
+                    _ => None,
+                }
+                .ok_or_else(|| (*line, format!("expected {{ ... }} after \"{}\"", name)))?;
+                blocks.push(Block {
+                    name,
+                    default,
+                    body: tokens[idx + 3..end].to_vec(),
+                });
+                default = false;
+                idx = end + 1;
+            }
+            Token::Ident(word) => {
+                default |= word == "default";
+                idx += 1;
+            }
+            Token::Punct(';') => idx += 1,
+            token => return Err((*line, format!("unexpected {:?}", token))),
+        }
+    }
+    Ok(blocks)
+}
+
+// The index of the bracket closing the one at `open`
+fn closing(tokens: &[(Token, usize)], open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (idx, (token, _)) in tokens.iter().enumerate().skip(open) {
+        match token {
+            Token::Punct('{') | Token::Punct('[') | Token::Punct('(') => depth += 1,
+            Token::Punct('}') | Token::Punct(']') | Token::Punct(')') => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(idx);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+// Statements this importer has no use for, e.g. `name[Group1] = "German";` and `modifier_map`
+fn skip_statement(tokens: &[(Token, usize)], mut idx: usize) -> usize {
+    while idx < tokens.len() {
+        match tokens[idx].0 {
+            Token::Punct(';') => return idx,
+            Token::Punct('{') | Token::Punct('[') | Token::Punct('(') => {
+                idx = closing(tokens, idx).unwrap_or(tokens.len()) + 1
+            }
+            _ => idx += 1,
+        }
+    }
+    idx
+}
+
+// Tokens and their line numbers, without `//`, `#` and `/* */` comments
+fn tokenize(contents: &str) -> Vec<(Token, usize)> {
+    let mut tokens = Vec::new();
+    let mut chars = contents.chars().peekable();
+    let mut line = 1;
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => line += 1,
+            c if c.is_whitespace() => {}
+            '/' if chars.peek() == Some(&'/') => {
+                while chars.peek().is_some_and(|c| *c != '\n') {
+                    chars.next();
+                }
+            }
+            '#' => {
+                while chars.peek().is_some_and(|c| *c != '\n') {
+                    chars.next();
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                let mut last = ' ';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    if last == '*' && c == '/' {
+                        break;
+                    }
+                    last = c;
+                }
+            }
+            '"' => {
+                let s: String = chars.by_ref().take_while(|c| *c != '"').collect();
+                line += s.matches('\n').count();
+                tokens.push((Token::Str(s), line));
+            }
+            '<' => {
+                let name = chars.by_ref().take_while(|c| *c != '>').collect();
+                tokens.push((Token::KeyName(name), line));
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut ident = c.to_string();
+                while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_') {
+                    ident.push(c);
+                }
+                tokens.push((Token::Ident(ident), line));
+            }
+            c => tokens.push((Token::Punct(c), line)),
+        }
+    }
+    tokens
+}
+
+// Keysyms that type nothing, e.g. NoSymbol and BackSpace, and unsupported deadkeys are `None`
+fn keysym(name: &str) -> Option<Sym> {
+    if let Some(accent) = name.strip_prefix("dead_") {
+        return dead_accent(accent).map(Sym::Dead);
+    }
+    if let Ok(idx) = KEYSYMS.binary_search_by_key(&name, |(name, _)| name) {
+        return std::char::from_u32(u32::from(KEYSYMS[idx].1)).map(Sym::Char);
+    }
+
+    let unicode = if let Some(hex) = name.strip_prefix('U') {
+        u32::from_str_radix(hex, 16).ok()?
+    } else if let Some(hex) = name.strip_prefix("0x") {
+        match u32::from_str_radix(hex, 16).ok()? {
+            keysym if keysym >= UNICODE_KEYSYM_OFFSET => keysym - UNICODE_KEYSYM_OFFSET,
+            // Keysyms for Latin-1 are the character itself
+            keysym if (0x20..0x7F).contains(&keysym) || (0xA0..=0xFF).contains(&keysym) => keysym,
+            _ => return None,
+        }
+    } else {
+        return None;
+    };
+    std::char::from_u32(unicode).map(Sym::Char)
+}
+
+// https://gitlab.freedesktop.org/xorg/proto/xorgproto/-/blob/master/include/X11/keysymdef.h
+fn dead_accent(name: &str) -> Option<Accent> {
+    match name {
+        "grave" => Some(Accent::GraveAccent),
+        "acute" => Some(Accent::AcuteAccent),
+        "circumflex" => Some(Accent::Circumflex),
+        "tilde" | "perispomeni" => Some(Accent::Tilde),
+        "breve" => Some(Accent::Breve),
+        "abovedot" => Some(Accent::DotAbove),
+        "diaeresis" => Some(Accent::Diaeresis),
+        "abovering" => Some(Accent::RingAbove),
+        "doubleacute" => Some(Accent::DoubleAcute),
+        "caron" => Some(Accent::Caron),
+        "cedilla" => Some(Accent::Cedilla),
+        "ogonek" => Some(Accent::Ogonek),
+        _ => None,
+    }
+}
+
+// XKB names keys by their position on the keyboard, e.g. <AD01> is the first key of the second
+// row from the top. https://www.x.org/releases/current/doc/xorg-docs/input/XKB-Config.html
+fn usage_for_key_name(name: &str) -> Option<u8> {
+    const AE: [u8; 12] = [
+        0x1E, 0x1F, 0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27, 0x2D, 0x2E,
+    ];
+    const AD: [u8; 12] = [
+        0x14, 0x1A, 0x08, 0x15, 0x17, 0x1C, 0x18, 0x0C, 0x12, 0x13, 0x2F, 0x30,
+    ];
+    const AC: [u8; 12] = [
+        0x04, 0x16, 0x07, 0x09, 0x0A, 0x0B, 0x0D, 0x0E, 0x0F, 0x33, 0x34, 0x31,
+    ];
+    const AB: [u8; 10] = [0x1D, 0x1B, 0x06, 0x19, 0x05, 0x11, 0x10, 0x36, 0x37, 0x38];
+
+    match name {
+        "TLDE" => return Some(0x35),
+        "BKSL" => return Some(0x31),
+        "LSGT" => return Some(0x64),
+        "SPCE" => return Some(0x2C),
+        _ => {}
+    }
+    let row: &[u8] = match name.get(..2)? {
+        "AE" => &AE,
+        "AD" => &AD,
+        "AC" => &AC,
+        "AB" => &AB,
+        _ => return None,
+    };
+    let column = name.get(2..)?.parse::<usize>().ok()?;
+    row.get(column.checked_sub(1)?).copied()
+}
@@ -22,6 +22,8 @@ const RDESC: [u8; 63] = [
 const ALPHA_NUMERIC: &'static str =
     "1234567890ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
 const SYMBOLS: &'static str = "\"#!$%&'()*+,-.\\/:;<=>?@[]^_`{|}~\"";
+const GERMAN_LATIN1: &'static str = "äöüÄÖÜßéÈâ°§";
+const FRENCH_LATIN1: &'static str = "àçèéùâêîôûëï²";
 
 lazy_static! {
     static ref X_LAYOUT_MAP: HashMap<&'static str, (&'static str, Option<&'static str>)> = hashmap! {
@@ -340,3 +342,5 @@ test_layout!(
     LAYOUT_UNITED_KINGDOM,
     SYMBOLS
 );
+test_layout!(test_latin1_layout_german, LAYOUT_GERMAN, GERMAN_LATIN1);
+test_layout!(test_latin1_layout_french, LAYOUT_FRENCH, FRENCH_LATIN1);
@@ -0,0 +1,92 @@
+use keyboard_layouts::{chord_to_hid_packets, Chord, ChordKey, Key, Modifier};
+
+#[test]
+fn presses_chord_characters_with_the_layout() {
+    // Z is where W is on a US keyboard
+    let chord = "Ctrl+Z".parse::<Chord>().unwrap();
+    let packets = chord_to_hid_packets("LAYOUT_FRENCH", &chord).unwrap();
+    assert_eq!(
+        &packets[..],
+        &[
+            0x01, 0, 0, 0, 0, 0, 0, 0, //
+            0x01, 0, 0x1A, 0, 0, 0, 0, 0, //
+            0, 0, 0, 0, 0, 0, 0, 0,
+        ][..]
+    );
+}
+
+#[test]
+fn parses_chords() {
+    let parse = |s: &str| s.parse::<Chord>().unwrap();
+
+    assert_eq!(
+        parse("Ctrl+Alt+Del"),
+        Chord::new(&[Modifier::Ctrl, Modifier::Alt], Key::Delete)
+    );
+    assert_eq!(parse("Win+R"), Chord::new(&[Modifier::Gui], 'r'));
+    assert_eq!(parse("Cmd+Space"), Chord::new(&[Modifier::Gui], Key::Space));
+    assert_eq!(parse("Ctrl++"), Chord::new(&[Modifier::Ctrl], '+'));
+    assert_eq!(
+        parse("Ctrl+Escape"),
+        Chord::new(&[Modifier::Ctrl], Key::Esc)
+    );
+    assert_eq!(
+        parse("Shift+Return"),
+        Chord::new(&[Modifier::Shift], Key::Enter)
+    );
+    assert_eq!(
+        parse("Win"),
+        Chord {
+            modifiers: vec![Modifier::Gui],
+            key: None,
+        }
+    );
+}
+
+#[test]
+fn parses_chords_with_spaces_around_the_keys() {
+    let parse = |s: &str| s.parse::<Chord>().unwrap();
+
+    assert_eq!(parse("Ctrl + z"), parse("Ctrl+z"));
+    assert_eq!(parse("Ctrl + z"), Chord::new(&[Modifier::Ctrl], 'z'));
+    assert_eq!(parse("Ctrl + Alt + Del"), parse("Ctrl+Alt+Del"));
+    assert_eq!(parse("Ctrl + +"), Chord::new(&[Modifier::Ctrl], '+'));
+    assert_eq!(parse("Ctrl+ "), Chord::new(&[Modifier::Ctrl], ' '));
+}
+
+#[test]
+fn rejects_invalid_chords() {
+    for chord in ["", "Ctrl+", "Ctrl+Foo", "Hyper+A", "Ctrl+Alt+Del+X"].iter() {
+        assert!(chord.parse::<Chord>().is_err(), "{} parsed", chord);
+    }
+}
+
+#[test]
+fn displays_chords_as_they_are_parsed() {
+    let chords = [
+        Chord::new(&[Modifier::Ctrl], 'z'),
+        Chord::new(&[Modifier::Ctrl, Modifier::Alt], Key::Delete),
+        Chord::new(&[Modifier::Gui], Key::PageUp),
+        Chord::new(&[Modifier::LeftShift], Key::A),
+        Chord::new(&[Modifier::Ctrl], '+'),
+    ];
+    let displayed = chords
+        .iter()
+        .map(|chord| chord.to_string())
+        .collect::<Vec<String>>();
+    assert_eq!(
+        displayed,
+        [
+            "Ctrl+Z",
+            "Ctrl+Alt+Delete",
+            "Gui+Page_Up",
+            "Left_Shift+KEY_A",
+            "Ctrl++"
+        ]
+    );
+
+    for (chord, displayed) in chords.iter().zip(displayed.iter()) {
+        assert_eq!(&displayed.parse::<Chord>().unwrap(), chord);
+    }
+    assert_eq!(ChordKey::Key(Key::Esc).to_string(), "Esc");
+}
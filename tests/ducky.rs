@@ -0,0 +1,84 @@
+use keyboard_layouts::{EncodeOptions, Error, Script, Step};
+
+use std::time::Duration;
+
+fn compile(script: Result<Script, Error>) -> Vec<Step> {
+    script
+        .unwrap()
+        .compile("LAYOUT_US_ENGLISH", EncodeOptions::default())
+        .unwrap()
+}
+
+fn assert_invalid(script: &str, expected_line: usize, expected_column: usize) {
+    match Script::parse_ducky(script) {
+        Err(Error::InvalidScript { line, column, .. }) => {
+            assert_eq!(
+                (line, column),
+                (expected_line, expected_column),
+                "{}",
+                script
+            )
+        }
+        other => panic!("expected InvalidScript for {}, got {:?}", script, other),
+    }
+}
+
+#[test]
+fn presses_key_lines() {
+    assert_eq!(
+        compile(Script::parse_ducky("GUI r")),
+        compile(Script::parse("PRESS Gui+r"))
+    );
+    assert_eq!(
+        compile(Script::parse_ducky("CTRL-ALT DELETE")),
+        compile(Script::parse("PRESS Ctrl+Alt+Delete"))
+    );
+    assert_eq!(
+        compile(Script::parse_ducky("ENTER\nDEL")),
+        compile(Script::parse("PRESS Enter Delete"))
+    );
+}
+
+#[test]
+fn separates_keys_with_any_whitespace() {
+    assert_eq!(
+        compile(Script::parse_ducky("GUI\u{3000}r")),
+        compile(Script::parse("PRESS Gui+r"))
+    );
+    assert_eq!(
+        compile(Script::parse_ducky("CTRL\u{a0}ALT DELETE")),
+        compile(Script::parse("PRESS Ctrl+Alt+Delete"))
+    );
+    // The columns count characters rather than bytes
+    assert_invalid("GUI\u{3000}FOO", 1, 5);
+    assert_invalid("CTRL\u{a0}ALT\u{a0}FOO", 1, 10);
+}
+
+#[test]
+fn delays_after_every_command() {
+    let steps = compile(Script::parse_ducky("DEFAULT_DELAY 100\nSTRING a\nENTER"));
+    let delay = Step::Delay(Duration::from_millis(100));
+    assert_eq!(steps.len(), 4);
+    assert_eq!(steps[1], delay);
+    assert_eq!(steps[3], delay);
+    assert_eq!(
+        compile(Script::parse_ducky("DEFAULTDELAY 100\nSTRING a\nENTER")),
+        steps
+    );
+}
+
+#[test]
+fn repeats_the_previous_command() {
+    assert_eq!(
+        compile(Script::parse_ducky("STRING ab\nREPEAT 2")),
+        compile(Script::parse("TYPE ababab"))
+    );
+    // Nothing to repeat
+    assert_invalid("REM start\nREPEAT 2", 2, 8);
+}
+
+#[test]
+fn rejects_unsupported_commands() {
+    assert_invalid("STRING a\n  ATTACKMODE HID", 2, 3);
+    assert_invalid("DELAY soon", 1, 7);
+}
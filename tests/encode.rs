@@ -0,0 +1,228 @@
+use keyboard_layouts::{
+    check_string, string_to_hid_packets, string_to_hid_packets_with_options, Accent, Alteration,
+    DeadKey, EncodeOptions, Error, Layout, LayoutExt, Untypeable,
+};
+
+const RELEASE: [u8; 8] = [0; 8];
+
+fn press(modifier: u8, key: u8) -> Vec<u8> {
+    let mut packets = vec![modifier, 0, key, 0, 0, 0, 0, 0];
+    packets.extend_from_slice(&RELEASE);
+    packets
+}
+
+#[test]
+fn types_latin1_characters() {
+    // KEY_QUOTE, with Shift for the capital
+    assert_eq!(
+        &string_to_hid_packets("LAYOUT_GERMAN", "ä").unwrap()[..],
+        &press(0, 0x34)[..]
+    );
+    assert_eq!(
+        &string_to_hid_packets("LAYOUT_GERMAN", "Ä").unwrap()[..],
+        &press(0x02, 0x34)[..]
+    );
+    // AltGr+M
+    assert_eq!(
+        &string_to_hid_packets("LAYOUT_GERMAN", "µ").unwrap()[..],
+        &press(0x40, 0x10)[..]
+    );
+}
+
+#[test]
+fn types_extra_characters() {
+    // € is listed as UNICODE_EXTRA, AltGr+E on a German keyboard
+    assert_eq!(
+        &string_to_hid_packets("LAYOUT_GERMAN", "€").unwrap()[..],
+        &press(0x40, 0x08)[..]
+    );
+}
+
+#[test]
+fn rejects_latin1_characters_the_layout_cannot_type() {
+    match string_to_hid_packets("LAYOUT_US_ENGLISH", "ä") {
+        Err(Error::InvalidCharacter('ä')) => {}
+        other => panic!("expected InvalidCharacter, got {:?}", other),
+    }
+}
+
+#[test]
+fn composes_ring_above_before_degree_sign() {
+    let us = keyboard_layouts::get_layout("LAYOUT_US_ENGLISH").unwrap();
+    // DegreeSign on KEY_TILDE and RingAbove on KEY_BACKSLASH
+    let layout = Layout::new(
+        us.shift_mask,
+        None,
+        None,
+        None,
+        Some(0x300),
+        0x3FF,
+        us.keycodes.to_vec(),
+        Vec::new(),
+        Vec::new(),
+        vec![
+            DeadKey::new(Accent::DegreeSign, 0x100, 0x35),
+            DeadKey::new(Accent::RingAbove, 0x200, 0x31),
+        ],
+    );
+
+    let packets = layout.encode("å").unwrap();
+    assert_eq!(&packets[..8], &[0, 0, 0x31, 0, 0, 0, 0, 0]);
+    assert_eq!(&packets[16..24], &[0, 0, 0x04, 0, 0, 0, 0, 0]);
+
+    assert_eq!(Accent::from_char('\u{030A}'), Some(Accent::RingAbove));
+    assert_eq!(Accent::from_char('°'), Some(Accent::DegreeSign));
+}
+
+fn with_untypeable(
+    string: &str,
+    untypeable: Untypeable,
+) -> Result<(Vec<u8>, Vec<Alteration>), Error> {
+    let options = EncodeOptions {
+        untypeable,
+        ..EncodeOptions::default()
+    };
+    string_to_hid_packets_with_options("LAYOUT_US_ENGLISH", string, options)
+        .map(|(packets, alterations)| (packets.to_vec(), alterations))
+}
+
+fn us_packets(string: &str) -> Vec<u8> {
+    string_to_hid_packets("LAYOUT_US_ENGLISH", string)
+        .unwrap()
+        .to_vec()
+}
+
+#[test]
+fn fails_on_untypeable_characters_by_default() {
+    match with_untypeable("a—b", Untypeable::Error) {
+        Err(Error::InvalidCharacter('—')) => {}
+        other => panic!("expected InvalidCharacter, got {:?}", other),
+    }
+}
+
+#[test]
+fn skips_untypeable_characters() {
+    let (packets, alterations) = with_untypeable("a—b", Untypeable::Skip).unwrap();
+    assert_eq!(packets, us_packets("ab"));
+    assert_eq!(
+        alterations,
+        vec![Alteration {
+            index: 1,
+            original: '—',
+            replacement: None,
+        }]
+    );
+}
+
+#[test]
+fn replaces_untypeable_characters() {
+    let (packets, alterations) = with_untypeable("aΩb", Untypeable::Replace('?')).unwrap();
+    assert_eq!(packets, us_packets("a?b"));
+    assert_eq!(
+        alterations,
+        vec![Alteration {
+            index: 1,
+            original: 'Ω',
+            replacement: Some("?".to_string()),
+        }]
+    );
+}
+
+#[test]
+fn transliterates_untypeable_characters_at_byte_indices() {
+    let (packets, alterations) = with_untypeable("ü—…x", Untypeable::Transliterate).unwrap();
+    assert_eq!(packets, us_packets("u-...x"));
+    let alteration = |index, original, replacement: &str| Alteration {
+        index,
+        original,
+        replacement: Some(replacement.to_string()),
+    };
+    // ü is 2 bytes long and — is 3
+    assert_eq!(
+        alterations,
+        vec![
+            alteration(0, 'ü', "u"),
+            alteration(2, '—', "-"),
+            alteration(5, '…', "..."),
+        ]
+    );
+
+    // Characters without an approximation still fail
+    match with_untypeable("Ω", Untypeable::Transliterate) {
+        Err(Error::InvalidCharacter('Ω')) => {}
+        other => panic!("expected InvalidCharacter, got {:?}", other),
+    }
+}
+
+#[test]
+fn reports_invalid_characters_at_byte_offsets() {
+    // Ω and ü are 2 bytes long
+    match check_string("LAYOUT_US_ENGLISH", "aΩbü€") {
+        Err(Error::InvalidCharacters(characters)) => {
+            assert_eq!(characters, vec![(1, 'Ω'), (4, 'ü'), (6, '€')])
+        }
+        other => panic!("expected InvalidCharacters, got {:?}", other),
+    }
+}
+
+#[test]
+fn rejects_ascii_characters_the_layout_has_no_key_for() {
+    // Italian keyboards have no '~' or '`'
+    match check_string("LAYOUT_ITALIAN", "a~") {
+        Err(Error::InvalidCharacters(characters)) => assert_eq!(characters, vec![(1, '~')]),
+        other => panic!("expected InvalidCharacters, got {:?}", other),
+    }
+    match string_to_hid_packets("LAYOUT_ITALIAN", "`") {
+        Err(Error::InvalidCharacter('`')) => {}
+        other => panic!("expected InvalidCharacter, got {:?}", other),
+    }
+
+    // So the untypeable handling applies to them
+    let options = EncodeOptions {
+        untypeable: Untypeable::Replace('-'),
+        ..EncodeOptions::default()
+    };
+    let (packets, alterations) =
+        string_to_hid_packets_with_options("LAYOUT_ITALIAN", "~", options).unwrap();
+    assert_eq!(
+        packets,
+        string_to_hid_packets("LAYOUT_ITALIAN", "-").unwrap()
+    );
+    assert_eq!(alterations.len(), 1);
+}
+
+#[test]
+fn types_decomposed_characters_as_composed_ones() {
+    // 'n' + COMBINING TILDE is the 'ñ' key on a Spanish keyboard
+    assert_eq!(
+        string_to_hid_packets("LAYOUT_SPANISH", "n\u{303}").unwrap(),
+        string_to_hid_packets("LAYOUT_SPANISH", "ñ").unwrap()
+    );
+    // At the byte offset of the base character
+    match check_string("LAYOUT_US_ENGLISH", "an\u{303}") {
+        Err(Error::InvalidCharacters(characters)) => assert_eq!(characters, vec![(1, 'ñ')]),
+        other => panic!("expected InvalidCharacters, got {:?}", other),
+    }
+    // Hangul jamo are all starters yet compose into one syllable
+    match check_string("LAYOUT_US_ENGLISH", "a\u{1100}\u{1161}b\u{1100}") {
+        Err(Error::InvalidCharacters(characters)) => {
+            assert_eq!(characters, vec![(1, '가'), (8, '\u{1100}')])
+        }
+        other => panic!("expected InvalidCharacters, got {:?}", other),
+    }
+}
+
+#[test]
+fn composes_characters_the_layout_does_not_list_from_deadkeys() {
+    // The grave accent deadkey, Shift+KEY_EQUAL, then KEY_N
+    let mut expected = press(0x02, 0x2E);
+    expected.extend(press(0, 0x11));
+    assert_eq!(
+        &string_to_hid_packets("LAYOUT_GERMAN", "ǹ").unwrap()[..],
+        &expected[..]
+    );
+    assert_eq!(
+        &string_to_hid_packets("LAYOUT_GERMAN", "n\u{300}").unwrap()[..],
+        &expected[..]
+    );
+}
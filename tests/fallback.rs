@@ -0,0 +1,87 @@
+use keyboard_layouts::{string_to_hid_packets_with_fallback, Fallback};
+
+const LEFT_ALT: u8 = 0x04;
+const NUMLOCK: u8 = 0x53;
+const RELEASE: [u8; 8] = [0; 8];
+
+// Presses the key and releases everything
+fn press(modifier: u8, key: u8) -> Vec<u8> {
+    let mut packets = vec![modifier, 0, key, 0, 0, 0, 0, 0];
+    packets.extend_from_slice(&RELEASE);
+    packets
+}
+
+// Presses the key and releases it, keeping the modifier held
+fn hold(modifier: u8, key: u8) -> Vec<u8> {
+    vec![
+        modifier, 0, key, 0, 0, 0, 0, 0, modifier, 0, 0, 0, 0, 0, 0, 0,
+    ]
+}
+
+fn packets(layout_key: &str, string: &str, fallback: Fallback) -> Vec<u8> {
+    string_to_hid_packets_with_fallback(layout_key, string, fallback)
+        .unwrap()
+        .to_vec()
+}
+
+#[test]
+fn types_alt_numpad_codes() {
+    // KEYPAD_2, KEYPAD_5 and KEYPAD_6, then Left-Alt is released
+    let mut expected = Vec::new();
+    for key in [0x5A, 0x5D, 0x5E].iter() {
+        expected.extend(hold(LEFT_ALT, *key));
+    }
+    expected.extend_from_slice(&RELEASE);
+
+    let fallback = Fallback::AltNumpad {
+        toggle_numlock: false,
+    };
+    assert_eq!(packets("LAYOUT_US_ENGLISH", "Ā", fallback), expected);
+}
+
+#[test]
+fn types_alt_numpad_codes_below_256_with_a_leading_zero() {
+    // 0233 between two NumLock presses
+    let mut expected = press(0, NUMLOCK);
+    for key in [0x62, 0x5A, 0x5B, 0x5B].iter() {
+        expected.extend(hold(LEFT_ALT, *key));
+    }
+    expected.extend_from_slice(&RELEASE);
+    expected.extend(press(0, NUMLOCK));
+
+    let fallback = Fallback::AltNumpad {
+        toggle_numlock: true,
+    };
+    assert_eq!(packets("LAYOUT_US_ENGLISH", "é", fallback), expected);
+}
+
+#[test]
+fn types_ctrl_shift_u_digits_with_the_layout() {
+    // U+017F: Ctrl+Shift+U, then the AZERTY digits 1 and 7 need Shift and f does not
+    let mut expected = press(0x12, 0x18);
+    expected.extend(press(0x02, 0x1E));
+    expected.extend(press(0x02, 0x24));
+    expected.extend(press(0, 0x09));
+    expected.extend(press(0, 0x2C));
+
+    assert_eq!(
+        packets("LAYOUT_FRENCH", "ſ", Fallback::CtrlShiftU),
+        expected
+    );
+}
+
+#[test]
+fn types_unicode_hex_input_surrogate_pairs() {
+    // U+1F600 is D83D DE00, typed on a US keyboard with Option held throughout
+    let mut expected = Vec::new();
+    for key in [0x07, 0x25, 0x20, 0x07, 0x07, 0x08, 0x27, 0x27].iter() {
+        expected.extend(hold(LEFT_ALT, *key));
+    }
+    expected.extend_from_slice(&RELEASE);
+
+    // The host's layout does not change the keys
+    assert_eq!(
+        packets("LAYOUT_FRENCH", "😀", Fallback::UnicodeHexInput),
+        expected
+    );
+}
@@ -0,0 +1,62 @@
+use keyboard_layouts::{Device, Error, KeyRollover, ReportFormat};
+
+// Left-Shift with A and Space
+const PACKET: [u8; 8] = [0x02, 0, 0x04, 0x2C, 0, 0, 0, 0];
+
+#[test]
+fn renders_boot_reports() {
+    let format = ReportFormat::default();
+    assert_eq!(format.report_len(), 8);
+    assert_eq!(&format.render(&PACKET).unwrap()[..], &PACKET[..]);
+
+    let format = ReportFormat {
+        rollover: KeyRollover::Boot,
+        report_id: Some(1),
+    };
+    assert_eq!(format.report_len(), 9);
+    assert_eq!(
+        &format.render(&PACKET).unwrap()[..],
+        &[0x01, 0x02, 0, 0x04, 0x2C, 0, 0, 0, 0][..]
+    );
+}
+
+#[test]
+fn renders_nkro_reports() {
+    let format = ReportFormat {
+        rollover: KeyRollover::Nkro { bytes: 8 },
+        report_id: Some(3),
+    };
+    assert_eq!(format.report_len(), 10);
+    // A is bit 4 of byte 0 and Space bit 4 of byte 5
+    assert_eq!(
+        &format.render(&PACKET).unwrap()[..],
+        &[0x03, 0x02, 0x10, 0, 0, 0, 0, 0x10, 0, 0][..]
+    );
+
+    let format = ReportFormat {
+        rollover: KeyRollover::Nkro { bytes: 4 },
+        report_id: None,
+    };
+    match format.render(&PACKET) {
+        Err(Error::UnsupportedByDevice(_)) => {}
+        other => panic!("expected UnsupportedByDevice, got {:?}", other),
+    }
+}
+
+#[test]
+fn rejects_partial_packets() {
+    let nkro = ReportFormat {
+        rollover: KeyRollover::Nkro { bytes: 8 },
+        report_id: None,
+    };
+    for format in [ReportFormat::default(), nkro].iter() {
+        match format.render(&[0u8]) {
+            Err(Error::InvalidPacketLength(1)) => {}
+            other => panic!("expected InvalidPacketLength, got {:?}", other),
+        }
+    }
+    match Device::Keyboard.keyboard_reports(&[0u8; 11]) {
+        Err(Error::InvalidPacketLength(11)) => {}
+        other => panic!("expected InvalidPacketLength, got {:?}", other),
+    }
+}
@@ -0,0 +1,135 @@
+use keyboard_layouts::{Accent, Error, LayoutExt};
+
+#[test]
+fn imports_xkb_levels_and_deadkeys() {
+    let layout = keyboard_layouts::load_xkb_layout("tests/xkb/intl", None).unwrap();
+
+    // AltGr+E, and Shift+AltGr+E as a Unicode keysym
+    assert_eq!(layout.decode(&layout.encode("€ẞ").unwrap()).unwrap(), "€ẞ");
+    // The acute deadkey on the ' key, then E
+    let e_acute = layout.encode("é").unwrap();
+    assert_eq!(&e_acute[..8], &[0, 0, 0x34, 0, 0, 0, 0, 0]);
+    assert_eq!(&e_acute[16..24], &[0, 0, 0x08, 0, 0, 0, 0, 0]);
+    // The Non-US \ key
+    assert_eq!(
+        &layout.encode("¦").unwrap()[..8],
+        &[0x42, 0, 100, 0, 0, 0, 0, 0]
+    );
+
+    let accents = layout
+        .deadkeys
+        .iter()
+        .map(|deadkey| deadkey.accent)
+        .collect::<Vec<Accent>>();
+    assert_eq!(
+        accents,
+        vec![
+            Accent::GraveAccent,
+            Accent::AcuteAccent,
+            Accent::Tilde,
+            Accent::Circumflex,
+            Accent::Diaeresis
+        ]
+    );
+}
+
+#[test]
+fn imports_xkb_variants() {
+    let layout = keyboard_layouts::load_xkb_layout("tests/xkb/intl", Some("nodeadkeys")).unwrap();
+    assert!(layout.deadkeys.is_empty());
+    assert!(keyboard_layouts::load_xkb_layout("tests/xkb/intl", Some("missing")).is_err());
+}
+
+#[test]
+fn rejects_imports_without_latin_letters() {
+    match keyboard_layouts::load_xkb_layout("tests/xkb/greek", None) {
+        Err(Error::InvalidLayoutFile(message)) => {
+            assert!(message.ends_with("no key types 'A'"), "{}", message)
+        }
+        Err(e) => panic!("expected InvalidLayoutFile, got {}", e),
+        Ok(_) => panic!("loaded a layout without Latin letters"),
+    }
+}
+
+#[test]
+fn rejects_malformed_xkb_includes() {
+    let error = |variant| match keyboard_layouts::load_xkb_layout("tests/xkb/malformed", variant) {
+        Err(Error::InvalidLayoutFile(message)) => message,
+        Err(e) => panic!("expected InvalidLayoutFile, got {}", e),
+        Ok(_) => panic!("loaded {:?}", variant),
+    };
+
+    // A file name starting with a multibyte character is read like any other
+    assert!(error(Some("accented")).contains("éx"));
+    assert!(error(Some("unclosed")).contains("line 8: malformed include \"base(basic\""));
+    assert!(error(Some("empty")).contains("malformed include"));
+}
+
+#[test]
+fn imports_klc_deadkey_tables() {
+    let layout = keyboard_layouts::load_klc_layout("tests/klc/TESTDE.klc").unwrap();
+
+    // AltGr+E from a hex column, Q from a literal one
+    assert_eq!(
+        layout.decode(&layout.encode("€Q@ẞ|").unwrap()).unwrap(),
+        "€Q@ẞ|"
+    );
+    // The circumflex deadkey types only what its DEADKEY section lists
+    assert_eq!(
+        layout.decode(&layout.encode("âÂ^").unwrap()).unwrap(),
+        "âÂ^"
+    );
+    assert_eq!(layout.latin1_keycodes['î' as usize - 0xA0], 0);
+    // There is no `Accent` for the macron deadkey
+    let accents = layout
+        .deadkeys
+        .iter()
+        .map(|deadkey| deadkey.accent)
+        .collect::<Vec<Accent>>();
+    assert_eq!(
+        accents,
+        vec![Accent::Circumflex, Accent::AcuteAccent, Accent::GraveAccent]
+    );
+}
+
+#[test]
+fn imports_keylayout_key_maps_and_actions() {
+    let layout = keyboard_layouts::load_keylayout("tests/keylayout/Test German.keylayout").unwrap();
+
+    // Option+E is typed with the Alt modifier
+    assert_eq!(
+        &layout.encode("€").unwrap()[..8],
+        &[0x40, 0, 0x08, 0, 0, 0, 0, 0]
+    );
+    // The ISO key map set swaps the keys left of 1 and left of Z back
+    assert_eq!(
+        &layout.encode("<").unwrap()[..8],
+        &[0, 0, 0x35, 0, 0, 0, 0, 0]
+    );
+    assert_eq!(
+        &layout.encode("°").unwrap()[..8],
+        &[0x02, 0, 0x64, 0, 0, 0, 0, 0]
+    );
+    // Deadkey states type exactly what their actions list
+    assert_eq!(
+        layout.decode(&layout.encode("âñÖ^~").unwrap()).unwrap(),
+        "âñÖ^~"
+    );
+    assert_eq!(layout.latin1_keycodes['Ë' as usize - 0xA0], 0);
+
+    let accents = layout
+        .deadkeys
+        .iter()
+        .map(|deadkey| deadkey.accent)
+        .collect::<Vec<Accent>>();
+    assert_eq!(
+        accents,
+        vec![
+            Accent::AcuteAccent,
+            Accent::Circumflex,
+            Accent::GraveAccent,
+            Accent::Diaeresis,
+            Accent::Tilde
+        ]
+    );
+}
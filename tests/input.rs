@@ -0,0 +1,42 @@
+use keyboard_layouts::{inputs_to_hid_packets, Input, Key};
+
+// Each named key is pressed with no modifiers and released
+fn key_packets(usage: u8) -> Vec<u8> {
+    vec![0, 0, usage, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+}
+
+#[test]
+fn presses_named_keys_by_usage() {
+    let keys = [
+        (Key::F5, 0x3E),
+        (Key::PageUp, 0x4B),
+        (Key::PageDown, 0x4E),
+        (Key::Right, 0x4F),
+        (Key::Left, 0x50),
+        (Key::Down, 0x51),
+        (Key::Up, 0x52),
+    ];
+    for (key, usage) in keys.iter() {
+        assert_eq!(key.usage(), *usage, "{:?}", key);
+        let packets = inputs_to_hid_packets("LAYOUT_US_ENGLISH", &[Input::Key(*key)]).unwrap();
+        assert_eq!(&packets[..], &key_packets(*usage)[..], "{:?}", key);
+    }
+}
+
+#[test]
+fn parses_named_keys() {
+    assert_eq!("F5".parse::<Key>(), Ok(Key::F5));
+    assert_eq!("KEY_PAGE_UP".parse::<Key>(), Ok(Key::PageUp));
+    assert_eq!("PAGEUP".parse::<Key>(), Ok(Key::PageUp));
+    assert_eq!("UP".parse::<Key>(), Ok(Key::Up));
+}
+
+#[test]
+fn presses_named_keys_between_text() {
+    let inputs = [Input::from("a"), Input::from(Key::Down), Input::from("a")];
+    // 'a' is where Q is on a US keyboard, Down is the same key on any layout
+    let packets = inputs_to_hid_packets("LAYOUT_FRENCH", &inputs).unwrap();
+    let a = [0, 0, 0x14, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    let expected = [&a[..], &key_packets(0x51)[..], &a[..]].concat();
+    assert_eq!(&packets[..], &expected[..]);
+}
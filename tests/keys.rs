@@ -0,0 +1,67 @@
+use keyboard_layouts::{key_and_modifier_to_chars, Accent, LayoutExt, LayoutId};
+
+const RIGHT_ALT: u8 = 0x40;
+const RIGHT_CTRL: u8 = 0x10;
+
+#[test]
+fn looks_up_the_characters_on_a_key() {
+    let a = key_and_modifier_to_chars("LAYOUT_US_ENGLISH", 0x04, 0).unwrap();
+    assert_eq!(a.chars, vec!['a']);
+    assert_eq!(a.deadkey, None);
+
+    let euro = key_and_modifier_to_chars("LAYOUT_GERMAN", 0x08, RIGHT_ALT).unwrap();
+    assert!(euro.chars.contains(&'€'), "{:?}", euro);
+
+    let enter = key_and_modifier_to_chars("LAYOUT_US_ENGLISH", 0x28, 0).unwrap();
+    assert_eq!(enter.chars, vec!['\n']);
+    let tab = key_and_modifier_to_chars("LAYOUT_US_ENGLISH", 0x2B, 0).unwrap();
+    assert_eq!(tab.chars, vec!['\t']);
+}
+
+#[test]
+fn looks_up_deadkeys() {
+    let circumflex = key_and_modifier_to_chars("LAYOUT_GERMAN", 0x35, 0).unwrap();
+    assert_eq!(circumflex.chars, Vec::<char>::new());
+    assert_eq!(circumflex.deadkey, Some(Accent::Circumflex));
+}
+
+#[test]
+fn leaves_out_control_characters_typed_with_ctrl() {
+    // Ctrl+Q sends DC1, which applications take as a shortcut rather than a character
+    let ctrl_q = key_and_modifier_to_chars("LAYOUT_US_ENGLISH", 0x14, RIGHT_CTRL).unwrap();
+    assert_eq!(ctrl_q.chars, Vec::<char>::new());
+    let bogus = key_and_modifier_to_chars("LAYOUT_US_ENGLISH", 0x54, RIGHT_CTRL).unwrap();
+    assert_eq!(bogus.chars, Vec::<char>::new());
+
+    for layout_id in LayoutId::ALL.iter() {
+        for key_chars in layout_id.all_key_chars() {
+            assert!(
+                key_chars
+                    .chars
+                    .iter()
+                    .all(|c| !c.is_control() || ['\n', '\t', '\x7F'].contains(c)),
+                "{:?}",
+                key_chars
+            );
+        }
+    }
+}
+
+#[test]
+fn lists_every_key_press_in_order() {
+    let key_chars = LayoutId::UsEnglish.all_key_chars();
+    let presses = key_chars
+        .iter()
+        .map(|key_chars| (key_chars.usage, key_chars.modifier))
+        .collect::<Vec<(u8, u8)>>();
+    let mut sorted = presses.clone();
+    sorted.sort_unstable();
+    sorted.dedup();
+    assert_eq!(presses, sorted);
+
+    let shift_a = key_chars
+        .iter()
+        .find(|key_chars| key_chars.chars == vec!['A'])
+        .unwrap();
+    assert_eq!((shift_a.usage, shift_a.modifier), (0x04, 0x02));
+}
@@ -0,0 +1,34 @@
+use keyboard_layouts::{get_layout, Error, InvalidLayoutId, LayoutExt, LayoutId};
+
+#[test]
+fn parses_layout_ids_as_they_are_displayed() {
+    for layout_id in LayoutId::ALL.iter() {
+        let name = layout_id.to_string();
+        assert_eq!(name, layout_id.name());
+        assert_eq!(name.parse::<LayoutId>(), Ok(*layout_id));
+    }
+    assert_eq!("LAYOUT_GERMAN".parse::<LayoutId>(), Ok(LayoutId::German));
+}
+
+#[test]
+fn rejects_unknown_layout_ids() {
+    let err = "LAYOUT_KLINGON".parse::<LayoutId>().unwrap_err();
+    assert_eq!(err, InvalidLayoutId("LAYOUT_KLINGON".to_string()));
+    assert_eq!(err.to_string(), "No layout defined for LAYOUT_KLINGON");
+    match Error::from(err) {
+        Error::InvalidLayoutKey(key) => assert_eq!(key, "LAYOUT_KLINGON"),
+        other => panic!("expected InvalidLayoutKey, got {:?}", other),
+    }
+}
+
+#[test]
+fn resolves_each_id_to_its_layout() {
+    for layout_id in LayoutId::ALL.iter() {
+        let layout = get_layout(layout_id.name()).unwrap();
+        assert!(std::ptr::eq(layout_id.layout(), layout), "{}", layout_id);
+    }
+    assert_eq!(
+        LayoutId::German.encode("z").unwrap(),
+        keyboard_layouts::string_to_hid_packets("LAYOUT_GERMAN", "z").unwrap()
+    );
+}
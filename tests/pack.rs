@@ -0,0 +1,48 @@
+use keyboard_layouts::{string_to_hid_packets, string_to_packed_hid_packets, HID_PACKET_LEN};
+
+fn packets(bytes: &[u8]) -> Vec<Vec<u8>> {
+    bytes
+        .chunks(HID_PACKET_LEN)
+        .map(|packet| packet.to_vec())
+        .collect()
+}
+
+#[test]
+fn holds_distinct_keys_until_the_run_ends() {
+    let packed = string_to_packed_hid_packets("LAYOUT_US_ENGLISH", "abc").unwrap();
+    let unpacked = string_to_hid_packets("LAYOUT_US_ENGLISH", "abc").unwrap();
+    assert!(packed.len() < unpacked.len());
+    assert_eq!(
+        packets(&packed),
+        vec![
+            vec![0, 0, 0x04, 0, 0, 0, 0, 0],
+            vec![0, 0, 0x04, 0x05, 0, 0, 0, 0],
+            vec![0, 0, 0x04, 0x05, 0x06, 0, 0, 0],
+            vec![0; HID_PACKET_LEN],
+        ]
+    );
+}
+
+#[test]
+fn releases_before_repeated_keys() {
+    // The host would not see a key that is already held as pressed again
+    let packed = string_to_packed_hid_packets("LAYOUT_US_ENGLISH", "aa").unwrap();
+    assert_eq!(
+        packed,
+        string_to_hid_packets("LAYOUT_US_ENGLISH", "aa").unwrap()
+    );
+}
+
+#[test]
+fn releases_before_changing_modifiers() {
+    let packed = string_to_packed_hid_packets("LAYOUT_US_ENGLISH", "aA").unwrap();
+    assert_eq!(
+        packets(&packed),
+        vec![
+            vec![0, 0, 0x04, 0, 0, 0, 0, 0],
+            vec![0; HID_PACKET_LEN],
+            vec![0x02, 0, 0x04, 0, 0, 0, 0, 0],
+            vec![0; HID_PACKET_LEN],
+        ]
+    );
+}
@@ -0,0 +1,95 @@
+use keyboard_layouts::{
+    available_layouts, get_layout, layout_from_toml, layout_to_toml, register_layout,
+    string_to_hid_packets, Accent, DeadKey, Error, Layout, LayoutId,
+};
+
+// The US layout with its keycodes and deadkeys changed, as read back from TOML
+fn us_layout_with(
+    change_keycodes: impl Fn(&mut Vec<u16>),
+    deadkeys: Vec<DeadKey>,
+) -> Result<Layout, Error> {
+    let us = get_layout("LAYOUT_US_ENGLISH").unwrap();
+    let mut keycodes = us.keycodes.to_vec();
+    change_keycodes(&mut keycodes);
+    let layout = Layout::new(
+        us.shift_mask,
+        us.alt_mask,
+        us.ctrl_mask,
+        us.non_us,
+        Some(0x300),
+        us.keycode_mask,
+        keycodes,
+        Vec::new(),
+        Vec::new(),
+        deadkeys,
+    );
+    layout_from_toml(&layout_to_toml(&layout).unwrap())
+}
+
+fn assert_invalid(result: Result<Layout, Error>) {
+    match result {
+        Err(Error::InvalidLayoutFile(_)) => {}
+        Err(e) => panic!("expected InvalidLayoutFile, got {}", e),
+        Ok(_) => panic!("expected InvalidLayoutFile, got a layout"),
+    }
+}
+
+#[test]
+fn reads_back_built_in_layouts() {
+    // Including those without some punctuation, e.g. '`' on the Italian layout
+    for layout_id in LayoutId::ALL.iter() {
+        let toml = layout_to_toml(layout_id.layout()).unwrap();
+        assert!(layout_from_toml(&toml).is_ok(), "{}", layout_id.name());
+    }
+}
+
+#[test]
+fn rejects_invalid_keycodes() {
+    let index = |c: char| c as usize - ' ' as usize;
+    assert!(us_layout_with(|_| {}, Vec::new()).is_ok());
+
+    // 'a' cannot be typed
+    assert_invalid(us_layout_with(
+        |keycodes| keycodes[index('a')] = 0,
+        Vec::new(),
+    ));
+    // Bits the layout does not use
+    assert_invalid(us_layout_with(
+        |keycodes| keycodes[index('!')] = 0x8000 | 0x1E,
+        Vec::new(),
+    ));
+    // Shift without a key
+    assert_invalid(us_layout_with(
+        |keycodes| keycodes[index('!')] = 0x0200,
+        Vec::new(),
+    ));
+    assert_invalid(us_layout_with(|keycodes| keycodes.truncate(95), Vec::new()));
+}
+
+#[test]
+fn rejects_deadkey_bits_outside_the_mask() {
+    let deadkey = |bits| vec![DeadKey::new(Accent::GraveAccent, bits, 0x35)];
+    assert!(us_layout_with(|_| {}, deadkey(0x100)).is_ok());
+    assert_invalid(us_layout_with(|_| {}, deadkey(0x400)));
+    assert_invalid(us_layout_with(|_| {}, deadkey(0)));
+}
+
+#[test]
+fn registers_layouts_once() {
+    let layout = us_layout_with(|_| {}, Vec::new()).unwrap();
+    register_layout("TEST_REGISTERED_US", layout).unwrap();
+    assert!(available_layouts().contains(&"TEST_REGISTERED_US"));
+    assert_eq!(
+        string_to_hid_packets("TEST_REGISTERED_US", "a").unwrap(),
+        string_to_hid_packets("LAYOUT_US_ENGLISH", "a").unwrap()
+    );
+
+    for layout_key in ["TEST_REGISTERED_US", "LAYOUT_US_ENGLISH"].iter() {
+        let layout = us_layout_with(|_| {}, Vec::new()).unwrap();
+        match register_layout(layout_key, layout) {
+            Err(Error::DuplicateLayout(key)) => assert_eq!(key, *layout_key),
+            Err(e) => panic!("expected DuplicateLayout, got {}", e),
+            Ok(_) => panic!("registered {} twice", layout_key),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use keyboard_layouts::{
+    Device, Error, MediaKey, SystemKey, COMPOSITE_REPORT_DESCRIPTOR,
+    SYSTEM_CONTROL_REPORT_DESCRIPTOR,
+};
+
+#[test]
+fn sends_media_keys_as_consumer_reports() {
+    let reports = Device::Composite
+        .media_key_reports(MediaKey::VolumeInc)
+        .unwrap();
+    // Report ID 2, then the Volume Increment usage as little endian
+    assert_eq!(reports[0][..], [0x02, 0xE9, 0x00]);
+    assert_eq!(reports[1][..], [0x02, 0x00, 0x00]);
+    assert_eq!(reports.len(), 2);
+}
+
+#[test]
+fn rejects_media_keys_on_keyboards() {
+    match Device::Keyboard.media_key_reports(MediaKey::VolumeInc) {
+        Err(Error::UnsupportedByDevice(_)) => {}
+        other => panic!("expected UnsupportedByDevice, got {:?}", other),
+    }
+}
+
+#[test]
+fn describes_the_composite_device() {
+    let descriptor = Device::Composite.report_descriptor().unwrap();
+    assert_eq!(descriptor.len(), 114);
+    assert_eq!(descriptor, &COMPOSITE_REPORT_DESCRIPTOR[..]);
+    assert_eq!(Device::Keyboard.report_descriptor().unwrap().len(), 63);
+
+    // Keyboard reports of the composite device start with report ID 1
+    let reports = Device::Composite
+        .keyboard_reports(&[0x02, 0, 0x04, 0, 0, 0, 0, 0])
+        .unwrap();
+    assert_eq!(reports[0][..], [0x01, 0x02, 0, 0x04, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn sends_system_keys_as_system_control_reports() {
+    let reports = Device::Composite
+        .system_key_reports(SystemKey::Sleep)
+        .unwrap();
+    // Report ID 3, then the System Sleep usage
+    assert_eq!(reports[0][..], [0x03, 0x82]);
+    assert_eq!(reports[1][..], [0x03, 0x00]);
+    assert_eq!(reports.len(), 2);
+
+    match Device::Keyboard.system_key_reports(SystemKey::Sleep) {
+        Err(Error::UnsupportedByDevice(_)) => {}
+        other => panic!("expected UnsupportedByDevice, got {:?}", other),
+    }
+}
+
+#[test]
+fn ends_the_composite_descriptor_with_system_control() {
+    let system_start = COMPOSITE_REPORT_DESCRIPTOR.len() - SYSTEM_CONTROL_REPORT_DESCRIPTOR.len();
+    assert_eq!(system_start, 90);
+    assert_eq!(
+        COMPOSITE_REPORT_DESCRIPTOR[system_start..],
+        SYSTEM_CONTROL_REPORT_DESCRIPTOR[..]
+    );
+}
@@ -0,0 +1,103 @@
+use bytes::Bytes;
+use keyboard_layouts::{Layout, LayoutExt, LayoutId, HID_PACKET_LEN};
+
+use std::collections::HashSet;
+
+// Every character the layout can type, each on its own and all of them in one string
+fn typeable_strings(layout: &Layout) -> Vec<String> {
+    let encodings = "\n\t"
+        .chars()
+        .chain(
+            (0x20..=0x7E)
+                .chain(0xA0..=0xFF)
+                .filter_map(std::char::from_u32),
+        )
+        .chain(
+            layout
+                .extra_keycodes
+                .iter()
+                .filter_map(|(unicode, _)| std::char::from_u32(u32::from(*unicode))),
+        )
+        .filter_map(|c| {
+            let packets = layout.encode(&c.to_string()).ok()?;
+            // Some headers define ASCII characters as 0, which presses nothing
+            Some((c, packets)).filter(|(_, packets)| packets.iter().any(|byte| *byte != 0))
+        })
+        .collect::<Vec<(char, Bytes)>>();
+
+    // A character composed from a deadkey is the deadkey's press and release, then its key's
+    let deadkeys = encodings
+        .iter()
+        .filter(|(_, packets)| packets.len() == 4 * HID_PACKET_LEN)
+        .map(|(_, packets)| packets.slice_to(2 * HID_PACKET_LEN))
+        .collect::<HashSet<Bytes>>();
+
+    // A character typed the same way as an earlier one, e.g. a no-break space as a space, decodes
+    // to that one. One typed with a key the header also lists as a deadkey decodes to nothing.
+    let mut typed = HashSet::new();
+    let characters = encodings
+        .into_iter()
+        .filter(|(_, packets)| !deadkeys.contains(packets) && typed.insert(packets.clone()))
+        .map(|(c, _)| c.to_string())
+        .collect::<Vec<String>>();
+
+    let mut strings = characters.clone();
+    strings.push(characters.concat());
+    strings
+}
+
+fn assert_round_trips(layout_key: &str, layout: &Layout) {
+    for string in typeable_strings(layout) {
+        let packets = layout.encode(&string).unwrap();
+        assert_eq!(layout.decode(&packets).unwrap(), string, "{}", layout_key);
+
+        let packets = layout.encode_packed(&string).unwrap();
+        assert_eq!(layout.decode(&packets).unwrap(), string, "{}", layout_key);
+    }
+}
+
+#[test]
+fn decodes_built_in_layouts() {
+    for layout_id in LayoutId::ALL.iter() {
+        assert_round_trips(layout_id.name(), layout_id.layout());
+    }
+}
+
+#[test]
+#[cfg(feature = "layout-files")]
+fn decodes_layout_files() {
+    let layout = keyboard_layouts::load_layout("layouts/LAYOUT_US_DVORAK.toml").unwrap();
+    assert_round_trips("LAYOUT_US_DVORAK", &layout);
+}
+
+#[test]
+fn decodes_xkb_layouts() {
+    for variant in [None, Some("nodeadkeys")].iter() {
+        let layout = keyboard_layouts::load_xkb_layout("tests/xkb/intl", *variant).unwrap();
+        assert_round_trips(&format!("intl({:?})", variant), &layout);
+    }
+}
+
+#[test]
+fn decodes_klc_layouts() {
+    let layout = keyboard_layouts::load_klc_layout("tests/klc/TESTDE.klc").unwrap();
+    assert_round_trips("TESTDE", &layout);
+}
+
+#[test]
+fn decodes_keylayouts() {
+    let layout = keyboard_layouts::load_keylayout("tests/keylayout/Test German.keylayout").unwrap();
+    assert_round_trips("Test German", &layout);
+}
+
+#[test]
+fn decodes_deadkeys_that_do_not_compose_as_their_accent() {
+    // The circumflex deadkey on KEY_TILDE, then KEY_X, on a German keyboard
+    let packets = [
+        0, 0, 0x35, 0, 0, 0, 0, 0, //
+        0, 0, 0, 0, 0, 0, 0, 0, //
+        0, 0, 0x1B, 0, 0, 0, 0, 0, //
+        0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+    assert_eq!(LayoutId::German.decode(&packets).unwrap(), "^x");
+}
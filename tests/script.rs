@@ -0,0 +1,99 @@
+use keyboard_layouts::{EncodeOptions, Error, Script, Step};
+
+fn compile(script: &str, layout_key: &str) -> Result<Vec<Step>, Error> {
+    Script::parse(script)?.compile(layout_key, EncodeOptions::default())
+}
+
+fn reports(script: &str) -> Vec<Vec<u8>> {
+    match compile(script, "LAYOUT_US_ENGLISH").unwrap().as_slice() {
+        [Step::Reports(reports)] => reports.iter().map(|report| report.to_vec()).collect(),
+        steps => panic!("expected a single step of reports, got {:?}", steps),
+    }
+}
+
+fn assert_invalid(result: Result<Vec<Step>, Error>, expected_line: usize, expected_column: usize) {
+    match result {
+        Err(Error::InvalidScript { line, column, .. }) => {
+            assert_eq!((line, column), (expected_line, expected_column))
+        }
+        other => panic!("expected InvalidScript, got {:?}", other),
+    }
+}
+
+#[test]
+fn reports_errors_at_their_line_and_column() {
+    assert_invalid(
+        compile("TYPE a\nPRESS Enter Foo", "LAYOUT_US_ENGLISH"),
+        2,
+        13,
+    );
+    assert_invalid(compile("# comment\n  JUMP", "LAYOUT_US_ENGLISH"), 2, 3);
+    assert_invalid(compile("DELAY soon", "LAYOUT_US_ENGLISH"), 1, 7);
+    // The character that cannot be typed rather than the start of the text
+    assert_invalid(compile("TYPE abΩ", "LAYOUT_US_ENGLISH"), 1, 8);
+}
+
+#[test]
+fn counts_columns_in_characters() {
+    assert_invalid(compile("TYPE äöΩ", "LAYOUT_GERMAN"), 1, 8);
+    assert_invalid(
+        compile("PRESS\u{3000}Enter\u{3000}Foo", "LAYOUT_US_ENGLISH"),
+        1,
+        13,
+    );
+}
+
+#[test]
+fn repeats_the_previous_command() {
+    assert_eq!(
+        reports("PRESS Enter\nREPEAT 2"),
+        reports("PRESS Enter Enter Enter")
+    );
+    assert_eq!(
+        reports("TYPE ab\nREPEAT 2"),
+        reports("TYPE ab\nTYPE ab\nTYPE ab")
+    );
+    assert_invalid(compile("REPEAT 2", "LAYOUT_US_ENGLISH"), 1, 8);
+    assert_invalid(
+        compile("TYPE a\nREPEAT 4294967295", "LAYOUT_US_ENGLISH"),
+        2,
+        8,
+    );
+}
+
+#[test]
+fn holds_keys_in_the_first_free_slots() {
+    assert_eq!(
+        reports("HOLD Alt Tab\nPRESS Right\nRELEASE"),
+        vec![
+            vec![0x04, 0, 0x2B, 0, 0, 0, 0, 0],
+            vec![0x04, 0, 0x4F, 0x2B, 0, 0, 0, 0],
+            vec![0x04, 0, 0x2B, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0],
+        ]
+    );
+    assert_eq!(
+        reports("HOLD Shift\nTYPE a\nRELEASE Shift"),
+        vec![
+            vec![0x02, 0, 0, 0, 0, 0, 0, 0],
+            vec![0x02, 0, 0x04, 0, 0, 0, 0, 0],
+            vec![0x02, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0],
+        ]
+    );
+}
+
+#[test]
+fn switches_layouts() {
+    // Y and Z are swapped on a German keyboard
+    assert_eq!(
+        reports("TYPE z\nLAYOUT LAYOUT_GERMAN\nTYPE z"),
+        vec![
+            vec![0, 0, 0x1D, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0x1C, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0],
+        ]
+    );
+    assert_invalid(compile("LAYOUT LAYOUT_NOWHERE", "LAYOUT_US_ENGLISH"), 1, 8);
+}